@@ -0,0 +1,82 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+
+use crate::attr::FieldOpts;
+
+pub fn expand(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = named_fields(&input)?;
+    let vis = &input.vis;
+    let ident = &input.ident;
+
+    let mut output = TokenStream::new();
+
+    for field in fields {
+        let opts = FieldOpts::parse(&field.attrs)?;
+        if opts.skip {
+            continue;
+        }
+
+        let field_ident = field.ident.as_ref().unwrap();
+        let accessor_name = opts
+            .rename
+            .as_ref()
+            .unwrap_or(field_ident)
+            .unraw()
+            .to_string();
+        let field_ty = &field.ty;
+        let trait_ident = format_ident!("Get{}", pascal_case(&accessor_name));
+        let fn_ident = format_ident!("get_{}", accessor_name);
+        let trait_doc = format!("Access the `{}` field of [{ident}].", field_ident.unraw());
+
+        output.extend(quote! {
+            #[doc = #trait_doc]
+            #vis trait #trait_ident {
+                fn #fn_ident(&self) -> &#field_ty;
+            }
+
+            impl<T> #trait_ident for ::implementation::Impl<T>
+                where ::implementation::Impl<T>: ::implementation::Project<#ident>
+            {
+                fn #fn_ident(&self) -> &#field_ty {
+                    &<Self as ::implementation::Project<#ident>>::project(self).#field_ident
+                }
+            }
+        });
+    }
+
+    Ok(output)
+}
+
+fn named_fields(
+    input: &syn::DeriveInput,
+) -> syn::Result<&syn::punctuated::Punctuated<syn::Field, syn::Token![,]>> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Accessors can't be derived for generic types",
+        ));
+    }
+    match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => Ok(&fields.named),
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            "Accessors can only be derived for structs with named fields",
+        )),
+    }
+}
+
+fn pascal_case(snake: &str) -> String {
+    snake
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap();
+            first.to_uppercase().chain(chars).collect::<String>()
+        })
+        .collect()
+}
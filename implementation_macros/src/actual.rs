@@ -0,0 +1,167 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    let explicit_deps = Punctuated::<syn::Path, syn::Token![,]>::parse_terminated.parse2(attr)?;
+    let mut item_impl: syn::ItemImpl = syn::parse2(input)?;
+
+    let trait_path = match &item_impl.trait_ {
+        Some((None, path, _)) => path.clone(),
+        _ => {
+            return Err(syn::Error::new(
+                item_impl.impl_token.span,
+                "#[actual] must be applied to a trait impl block",
+            ))
+        }
+    };
+
+    make_generic_self_ty(&mut item_impl)?;
+
+    let mut deps = Dependencies {
+        trait_path: path_key(&trait_path),
+        keys: vec![],
+        paths: vec![],
+    };
+    for dep in explicit_deps {
+        deps.push(dep);
+    }
+    for item in &item_impl.items {
+        deps.visit_impl_item(item);
+    }
+
+    if !deps.paths.is_empty() {
+        let self_ty = &item_impl.self_ty;
+        let dep_paths = &deps.paths;
+        item_impl
+            .generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote! {
+                #self_ty: #(#dep_paths)+*
+            });
+    }
+
+    Ok(quote! { #item_impl })
+}
+
+/// Turn `Impl` into `Impl<T>`, introducing the generic parameter `T`.
+fn make_generic_self_ty(item_impl: &mut syn::ItemImpl) -> syn::Result<()> {
+    let self_ty_span = item_impl.self_ty.span();
+    let segment = match item_impl.self_ty.as_mut() {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            type_path.path.segments.last_mut().unwrap()
+        }
+        _ => {
+            return Err(syn::Error::new(
+                self_ty_span,
+                "expected `Impl` or `Impl<T>`",
+            ))
+        }
+    };
+    if segment.ident != "Impl" {
+        return Err(syn::Error::new(
+            self_ty_span,
+            "expected `Impl` or `Impl<T>`",
+        ));
+    }
+    if !segment.arguments.is_none() {
+        return Ok(());
+    }
+
+    let param: syn::Ident = syn::parse_quote!(T);
+    if item_impl.generics.type_params().any(|p| p.ident == param) {
+        return Err(syn::Error::new(
+            self_ty_span,
+            "`T` is already declared, write the self type as `Impl<T>`",
+        ));
+    }
+    segment.arguments = syn::PathArguments::AngleBracketed(syn::parse_quote!(<#param>));
+    item_impl.generics.params.push(syn::parse_quote!(#param));
+
+    Ok(())
+}
+
+/// Collects the dependency traits called on `self` using fully qualified syntax.
+struct Dependencies {
+    trait_path: String,
+    keys: Vec<String>,
+    paths: Vec<syn::Path>,
+}
+
+impl Dependencies {
+    fn push(&mut self, path: syn::Path) {
+        let key = path_key(&path);
+        if key == self.trait_path || self.keys.contains(&key) {
+            return;
+        }
+        self.keys.push(key);
+        self.paths.push(path);
+    }
+}
+
+impl<'ast> Visit<'ast> for Dependencies {
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(func) = call.func.as_ref() {
+            if let Some(dep) = dependency_trait(func, call.args.first()) {
+                self.push(dep);
+            }
+        }
+        syn::visit::visit_expr_call(self, call);
+    }
+}
+
+/// Recognizes `<Self as Dep>::method(..)` and `Dep::method(self, ..)`, where `Dep` is UpperCamelCase.
+fn dependency_trait(func: &syn::ExprPath, first_arg: Option<&syn::Expr>) -> Option<syn::Path> {
+    if let Some(qself) = &func.qself {
+        if !is_ident(&qself.ty, "Self") || qself.position == 0 {
+            return None;
+        }
+        return Some(syn::Path {
+            leading_colon: func.path.leading_colon,
+            segments: func
+                .path
+                .segments
+                .iter()
+                .take(qself.position)
+                .cloned()
+                .collect(),
+        });
+    }
+
+    match first_arg {
+        Some(syn::Expr::Path(arg)) if arg.qself.is_none() && arg.path.is_ident("self") => {}
+        _ => return None,
+    }
+
+    let segments = &func.path.segments;
+    if segments.len() < 2 || segments[0].ident == "Self" || segments[0].ident == "Impl" {
+        return None;
+    }
+
+    // Free functions like `helpers::log(self)` also take `self` first, and are told apart from trait
+    // methods by naming convention: the segment before the method must be an UpperCamelCase trait name.
+    let dep = &segments[segments.len() - 2].ident;
+    if !dep
+        .to_string()
+        .starts_with(|c: char| c.is_ascii_uppercase())
+    {
+        return None;
+    }
+
+    Some(syn::Path {
+        leading_colon: func.path.leading_colon,
+        segments: segments.iter().take(segments.len() - 1).cloned().collect(),
+    })
+}
+
+fn is_ident(ty: &syn::Type, ident: &str) -> bool {
+    matches!(ty, syn::Type::Path(type_path) if type_path.qself.is_none() && type_path.path.is_ident(ident))
+}
+
+fn path_key(path: &syn::Path) -> String {
+    path.to_token_stream().to_string()
+}
@@ -0,0 +1,68 @@
+/// Options given to a field through `#[implementation(...)]`.
+#[derive(Default)]
+pub struct FieldOpts {
+    pub skip: bool,
+    pub delegate: bool,
+    pub provide: bool,
+    pub rename: Option<syn::Ident>,
+}
+
+impl FieldOpts {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut opts = Self::default();
+        for attr in attrs {
+            if !attr.path().is_ident("implementation") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    opts.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("delegate") {
+                    opts.delegate = true;
+                    Ok(())
+                } else if meta.path.is_ident("provide") {
+                    opts.provide = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    let name: syn::LitStr = meta.value()?.parse()?;
+                    opts.rename = Some(name.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unrecognized implementation option"))
+                }
+            })?;
+        }
+        Ok(opts)
+    }
+}
+
+/// Options given to a method through `#[implementation(...)]`.
+#[derive(Default)]
+pub struct MethodOpts {
+    pub record: Vec<syn::Ident>,
+}
+
+impl MethodOpts {
+    /// Parse and remove the `#[implementation(...)]` attributes.
+    pub fn take(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Self> {
+        let mut opts = Self::default();
+        for attr in attrs.iter() {
+            if !attr.path().is_ident("implementation") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("record") {
+                    meta.parse_nested_meta(|arg| {
+                        opts.record.push(arg.path.require_ident()?.clone());
+                        Ok(())
+                    })
+                } else {
+                    Err(meta.error("unrecognized implementation option"))
+                }
+            })?;
+        }
+        attrs.retain(|attr| !attr.path().is_ident("implementation"));
+        Ok(opts)
+    }
+}
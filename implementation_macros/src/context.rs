@@ -0,0 +1,55 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "#[context] takes no arguments",
+        ));
+    }
+    let item_type: syn::ItemType = syn::parse2(input)?;
+    if !item_type.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item_type.generics,
+            "#[context] doesn't support generic type aliases",
+        ));
+    }
+    let syn::Type::Tuple(tuple) = item_type.ty.as_ref() else {
+        return Err(syn::Error::new_spanned(
+            &item_type.ty,
+            "#[context] must be applied to a type alias of a tuple",
+        ));
+    };
+
+    let ty = &item_type.ty;
+    let mut keys = vec![];
+    let mut impls = vec![];
+
+    for (index, elem) in tuple.elems.iter().enumerate() {
+        // Two elements of the same type would make the projection ambiguous.
+        let key = elem.to_token_stream().to_string();
+        if keys.contains(&key) {
+            return Err(syn::Error::new_spanned(
+                elem,
+                "a type can only appear once in a context",
+            ));
+        }
+        keys.push(key);
+
+        let index = syn::Index::from(index);
+        impls.push(quote! {
+            impl ::implementation::Project<#elem> for ::implementation::Impl<#ty> {
+                fn project(&self) -> &::implementation::Impl<#elem> {
+                    ::implementation::Impl::from_ref(&self.#index)
+                }
+            }
+        });
+    }
+
+    Ok(quote! {
+        #item_type
+
+        #(#impls)*
+    })
+}
@@ -0,0 +1,164 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+
+use crate::util::{contains_impl_trait, reject_self, rename_args};
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "#[dispatch] takes no arguments",
+        ));
+    }
+    let item_trait: syn::ItemTrait = syn::parse2(input)?;
+
+    let trait_ident = &item_trait.ident;
+    let (_, trait_ty_generics, _) = item_trait.generics.split_for_impl();
+    let (a, f) = fresh_type_params(&item_trait);
+    let actual_trait = quote! { <#a as #trait_ident #trait_ty_generics> };
+    let fake_trait = quote! { <#f as #trait_ident #trait_ty_generics> };
+
+    let mut items = vec![];
+    let mut assoc_bindings = vec![];
+
+    for item in &item_trait.items {
+        match item {
+            syn::TraitItem::Fn(item_fn) => {
+                items.push(dispatch_fn(item_fn, &actual_trait, &fake_trait)?);
+            }
+            syn::TraitItem::Type(item_type) if item_type.generics.params.is_empty() => {
+                let ident = &item_type.ident;
+                items.push(quote! {
+                    type #ident = #actual_trait::#ident;
+                });
+                assoc_bindings.push(quote! {
+                    #ident = #actual_trait::#ident
+                });
+            }
+            syn::TraitItem::Const(item_const) => {
+                let ident = &item_const.ident;
+                let ty = &item_const.ty;
+                items.push(quote! {
+                    const #ident: #ty = #actual_trait::#ident;
+                });
+            }
+            _ => return Err(syn::Error::new(item.span(), "unsupported trait item")),
+        }
+    }
+
+    let fake_bound = match trait_ty_generics_args(&item_trait.generics) {
+        args if args.is_empty() && assoc_bindings.is_empty() => quote! { #trait_ident },
+        args => quote! { #trait_ident<#(#args,)* #(#assoc_bindings),*> },
+    };
+
+    let mut generics = item_trait.generics.clone();
+    generics.params.push(syn::parse_quote!(#a));
+    generics.params.push(syn::parse_quote!(#f));
+    let where_clause = generics.make_where_clause();
+    where_clause.predicates.push(syn::parse_quote! {
+        #a: #trait_ident #trait_ty_generics
+    });
+    where_clause.predicates.push(syn::parse_quote! {
+        #f: #fake_bound
+    });
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #item_trait
+
+        impl #impl_generics #trait_ident #trait_ty_generics for ::implementation::Dispatch<#a, #f> #where_clause {
+            #(#items)*
+        }
+    })
+}
+
+fn dispatch_fn(
+    item_fn: &syn::TraitItemFn,
+    actual_trait: &TokenStream,
+    fake_trait: &TokenStream,
+) -> syn::Result<TokenStream> {
+    reject_self(&item_fn.sig, "dispatch")?;
+    let mut sig = item_fn.sig.clone();
+    if let syn::ReturnType::Type(_, ty) = &sig.output {
+        if contains_impl_trait(ty) {
+            return Err(syn::Error::new(
+                ty.span(),
+                "methods returning `impl Trait` cannot be dispatched",
+            ));
+        }
+    }
+    match sig.receiver() {
+        Some(receiver) if receiver.colon_token.is_some() => {
+            return Err(syn::Error::new(
+                receiver.span(),
+                "unsupported receiver type",
+            ));
+        }
+        Some(_) => {}
+        None => {
+            return Err(syn::Error::new(
+                sig.span(),
+                "methods without a receiver cannot be dispatched",
+            ));
+        }
+    }
+
+    let args = rename_args(&mut sig);
+    let ident = &sig.ident;
+    let dot_await = sig.asyncness.map(|_| quote! { .await });
+
+    Ok(quote! {
+        #sig {
+            match self {
+                ::implementation::Dispatch::Actual(actual) => #actual_trait::#ident(actual, #(#args),*) #dot_await,
+                ::implementation::Dispatch::Fake(fake) => #fake_trait::#ident(fake, #(#args),*) #dot_await,
+            }
+        }
+    })
+}
+
+/// Type parameter names for the actual and fake implementations that are not already used by the trait or its methods.
+fn fresh_type_params(item_trait: &syn::ItemTrait) -> (syn::Ident, syn::Ident) {
+    let method_generics = item_trait.items.iter().filter_map(|item| match item {
+        syn::TraitItem::Fn(item_fn) => Some(&item_fn.sig.generics),
+        _ => None,
+    });
+    let used: Vec<&syn::Ident> = core::iter::once(&item_trait.generics)
+        .chain(method_generics)
+        .flat_map(|generics| generics.type_params().map(|param| &param.ident))
+        .collect();
+    let is_fresh = |ident: &syn::Ident| !used.contains(&ident);
+    let fresh = |name: &str| {
+        (0usize..)
+            .map(|n| match n {
+                0 => format_ident!("{}", name),
+                n => format_ident!("{}{}", name, n),
+            })
+            .find(is_fresh)
+            .unwrap()
+    };
+    (fresh("A"), fresh("F"))
+}
+
+/// The generic arguments of the trait, as they appear in a bound.
+fn trait_ty_generics_args(generics: &syn::Generics) -> Vec<TokenStream> {
+    generics
+        .params
+        .iter()
+        .map(|param| match param {
+            syn::GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote! { #lifetime }
+            }
+            syn::GenericParam::Type(param) => {
+                let ident = &param.ident;
+                quote! { #ident }
+            }
+            syn::GenericParam::Const(param) => {
+                let ident = &param.ident;
+                quote! { #ident }
+            }
+        })
+        .collect()
+}
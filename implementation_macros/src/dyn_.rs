@@ -0,0 +1,253 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+
+use crate::util::{contains_impl_trait, fresh_type_param, mentions_self, rename_args};
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(attr, "#[dyn_] takes no arguments"));
+    }
+    let item_trait: syn::ItemTrait = syn::parse2(input)?;
+
+    let vis = &item_trait.vis;
+    let trait_ident = &item_trait.ident;
+    let dyn_ident = format_ident!("Dyn{}", trait_ident);
+    let dyn_doc = format!(
+        "Object-safe companion of [{trait_ident}], implemented for every `Impl<T>` that implements [{trait_ident}]."
+    );
+    let (_, trait_ty_generics, trait_where_clause) = item_trait.generics.split_for_impl();
+    let generics = &item_trait.generics;
+    let t = fresh_type_param(generics);
+    let actual_trait = quote! {
+        <::implementation::Impl<#t> as #trait_ident #trait_ty_generics>
+    };
+    let d = {
+        let mut generics = generics.clone();
+        generics.params.push(syn::parse_quote!(#t));
+        fresh_type_param(&generics)
+    };
+    let dyn_trait = quote! { <#d as #dyn_ident #trait_ty_generics> };
+
+    // Other supertraits, like `Sized` or `Clone`, could make the companion unusable as a trait object.
+    let dyn_supertraits: Vec<&syn::TypeParamBound> = item_trait
+        .supertraits
+        .iter()
+        .filter(|bound| is_auto_trait_or_lifetime(bound))
+        .collect();
+    let dyn_colon_token = if dyn_supertraits.is_empty() {
+        None
+    } else {
+        Some(quote! { : })
+    };
+
+    let mut dyn_items = vec![];
+    let mut bridge_items = vec![];
+    let mut back_items = vec![];
+    // `Box<D>` only inherits the auto traits and lifetime bounds of `D`.
+    let mut back_complete = item_trait
+        .supertraits
+        .iter()
+        .all(|bound| is_auto_trait_or_lifetime(bound) || is_sized(bound));
+
+    for item in &item_trait.items {
+        match item {
+            syn::TraitItem::Fn(item_fn) => match dyn_fn(&item_fn.sig) {
+                Some(dyn_fn) => {
+                    let DynFn {
+                        dyn_sig,
+                        sig,
+                        receiver,
+                        args,
+                    } = dyn_fn;
+                    let ident = &sig.ident;
+                    let (bridge_receiver, back_receiver) = match receiver {
+                        DynReceiver::Ref => (quote! { self }, quote! { &**self }),
+                        DynReceiver::Mut => (quote! { self }, quote! { &mut **self }),
+                        DynReceiver::Owned => (quote! { *self }, quote! { self }),
+                        DynReceiver::Boxed => (quote! { self }, quote! { *self }),
+                    };
+                    dyn_items.push(quote! { #dyn_sig; });
+                    bridge_items.push(quote! {
+                        #dyn_sig {
+                            #actual_trait::#ident(#bridge_receiver, #(#args),*)
+                        }
+                    });
+                    back_items.push(quote! {
+                        #sig {
+                            #dyn_trait::#ident(#back_receiver, #(#args),*)
+                        }
+                    });
+                }
+                None => back_complete = false,
+            },
+            syn::TraitItem::Type(item_type) if item_type.generics.params.is_empty() => {
+                let ident = &item_type.ident;
+                let colon_token = &item_type.colon_token;
+                let bounds = &item_type.bounds;
+                dyn_items.push(quote! { type #ident #colon_token #bounds; });
+                bridge_items.push(quote! { type #ident = #actual_trait::#ident; });
+                back_items.push(quote! { type #ident = #dyn_trait::#ident; });
+            }
+            _ => back_complete = false,
+        }
+    }
+
+    let mut bridge_generics = generics.clone();
+    bridge_generics.params.push(syn::parse_quote!(#t));
+    bridge_generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! {
+            ::implementation::Impl<#t>: #trait_ident #trait_ty_generics
+        });
+    let (bridge_impl_generics, _, bridge_where_clause) = bridge_generics.split_for_impl();
+
+    // Only a trait that is object safe in its entirety, and whose supertraits carry over to `Box<D>`, can be
+    // implemented back from its companion.
+    let back_impl = if back_complete {
+        let mut back_generics = generics.clone();
+        back_generics
+            .params
+            .push(syn::parse_quote!(#d: #dyn_ident #trait_ty_generics + ?Sized));
+        let (back_impl_generics, _, back_where_clause) = back_generics.split_for_impl();
+        Some(quote! {
+            impl #back_impl_generics #trait_ident #trait_ty_generics for ::implementation::__private::Box<#d> #back_where_clause {
+                #(#back_items)*
+            }
+        })
+    } else {
+        None
+    };
+
+    Ok(quote! {
+        #item_trait
+
+        #[doc = #dyn_doc]
+        #vis trait #dyn_ident #generics #dyn_colon_token #(#dyn_supertraits)+* #trait_where_clause {
+            #(#dyn_items)*
+        }
+
+        impl #bridge_impl_generics #dyn_ident #trait_ty_generics for ::implementation::Impl<#t> #bridge_where_clause {
+            #(#bridge_items)*
+        }
+
+        #back_impl
+    })
+}
+
+enum DynReceiver {
+    Ref,
+    Mut,
+    Owned,
+    Boxed,
+}
+
+struct DynFn {
+    /// The signature in the companion trait.
+    dyn_sig: syn::Signature,
+    /// The original signature, with renamed arguments.
+    sig: syn::Signature,
+    receiver: DynReceiver,
+    args: Vec<syn::Ident>,
+}
+
+/// The object-safe counterpart of a method, or `None` when the method can't be called through a `dyn`.
+fn dyn_fn(sig: &syn::Signature) -> Option<DynFn> {
+    if sig.asyncness.is_some()
+        || sig.generics.type_params().next().is_some()
+        || sig.generics.const_params().next().is_some()
+        || requires_sized(sig)
+    {
+        return None;
+    }
+
+    let receiver = sig.receiver()?;
+    let receiver = match (
+        &receiver.colon_token,
+        &receiver.reference,
+        &receiver.mutability,
+    ) {
+        (None, None, _) => DynReceiver::Owned,
+        (None, Some(_), None) => DynReceiver::Ref,
+        (None, Some(_), Some(_)) => DynReceiver::Mut,
+        (Some(_), ..) if is_box_self(&receiver.ty) => DynReceiver::Boxed,
+        (Some(_), ..) => return None,
+    };
+
+    for input in sig.inputs.iter().skip(1) {
+        let syn::FnArg::Typed(pat_type) = input else {
+            return None;
+        };
+        if mentions_self(pat_type.ty.to_token_stream()) || contains_impl_trait(&pat_type.ty) {
+            return None;
+        }
+    }
+    if let syn::ReturnType::Type(_, ty) = &sig.output {
+        if mentions_self(ty.to_token_stream()) || contains_impl_trait(ty) {
+            return None;
+        }
+    }
+
+    let mut sig = sig.clone();
+    let args = rename_args(&mut sig);
+    let mut dyn_sig = sig.clone();
+    if let DynReceiver::Owned = receiver {
+        let mutability = sig.receiver().unwrap().mutability;
+        *dyn_sig.inputs.first_mut().unwrap() =
+            syn::parse_quote!(#mutability self: ::implementation::__private::Box<Self>);
+    }
+
+    Some(DynFn {
+        dyn_sig,
+        sig,
+        receiver,
+        args,
+    })
+}
+
+fn requires_sized(sig: &syn::Signature) -> bool {
+    sig.generics.where_clause.iter().any(|where_clause| {
+        where_clause.predicates.iter().any(|predicate| match predicate {
+            syn::WherePredicate::Type(predicate_type) => {
+                matches!(&predicate_type.bounded_ty, syn::Type::Path(path) if path.path.is_ident("Self"))
+                    && predicate_type.bounds.iter().any(is_sized)
+            }
+            _ => false,
+        })
+    })
+}
+
+fn is_box_self(ty: &syn::Type) -> bool {
+    let syn::Type::Path(type_path) = ty else {
+        return false;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return false;
+    };
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return false;
+    };
+    segment.ident == "Box"
+        && args.args.len() == 1
+        && matches!(&args.args[0], syn::GenericArgument::Type(syn::Type::Path(path)) if path.path.is_ident("Self"))
+}
+
+fn is_sized(bound: &syn::TypeParamBound) -> bool {
+    matches!(bound, syn::TypeParamBound::Trait(bound) if bound.path.segments.last().is_some_and(|segment| segment.ident == "Sized"))
+}
+
+fn is_auto_trait_or_lifetime(bound: &syn::TypeParamBound) -> bool {
+    match bound {
+        syn::TypeParamBound::Lifetime(_) => true,
+        syn::TypeParamBound::Trait(bound) => {
+            matches!(bound.modifier, syn::TraitBoundModifier::None)
+                && bound.path.segments.last().is_some_and(|segment| {
+                    segment.arguments.is_none()
+                        && ["Send", "Sync", "Unpin", "UnwindSafe", "RefUnwindSafe"]
+                            .iter()
+                            .any(|auto| segment.ident == auto)
+                })
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,128 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+
+use crate::util::{forward_assoc_item, fresh_type_param, reject_self, rename_args};
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(attr, "#[fake] takes no arguments"));
+    }
+    let item_trait: syn::ItemTrait = syn::parse2(input)?;
+
+    let vis = &item_trait.vis;
+    let trait_ident = &item_trait.ident;
+    let fake_ident = format_ident!("{}Fake", trait_ident);
+    let fake_doc = format!(
+        "Fake implementation of [{trait_ident}] for `Fake<Self>`, where each method defaults to the actual implementation."
+    );
+    let (_, trait_ty_generics, _) = item_trait.generics.split_for_impl();
+
+    let mut fake_trait_generics = item_trait.generics.clone();
+    fake_trait_generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! {
+            ::implementation::Impl<Self>: #trait_ident #trait_ty_generics
+        });
+    let fake_trait_where_clause = &fake_trait_generics.where_clause;
+    let fake_trait_actual = quote! {
+        <::implementation::Impl<Self> as #trait_ident #trait_ty_generics>
+    };
+
+    let t = fresh_type_param(&item_trait.generics);
+    let mut impl_generics = item_trait.generics.clone();
+    impl_generics.params.push(syn::parse_quote!(#t));
+    let impl_where_clause = impl_generics.make_where_clause();
+    impl_where_clause.predicates.push(syn::parse_quote! {
+        #t: #fake_ident #trait_ty_generics
+    });
+    impl_where_clause.predicates.push(syn::parse_quote! {
+        ::implementation::Impl<#t>: #trait_ident #trait_ty_generics
+    });
+    let (impl_generics, _, impl_where_clause) = impl_generics.split_for_impl();
+    let impl_actual = quote! {
+        <::implementation::Impl<#t> as #trait_ident #trait_ty_generics>
+    };
+
+    let mut fake_trait_items = vec![];
+    let mut impl_items = vec![];
+
+    for item in &item_trait.items {
+        let syn::TraitItem::Fn(item_fn) = item else {
+            impl_items.push(
+                forward_assoc_item(item, &impl_actual)
+                    .ok_or_else(|| syn::Error::new(item.span(), "unsupported trait item"))?,
+            );
+            continue;
+        };
+
+        reject_self(&item_fn.sig, "fake")?;
+        let mut sig = item_fn.sig.clone();
+        let args = rename_args(&mut sig);
+        let ident = &sig.ident;
+        let dot_await = sig.asyncness.map(|_| quote! { .await });
+
+        let impl_sig = sig.clone();
+        let receiver = sig.receiver().map(|_| quote! { self });
+        let impl_args = receiver
+            .into_iter()
+            .chain(args.iter().map(|arg| quote! { #arg }));
+        impl_items.push(quote! {
+            #impl_sig {
+                <#t as #fake_ident #trait_ty_generics>::#ident(#(#impl_args),*) #dot_await
+            }
+        });
+
+        // The receiver of the fake trait method is the `Fake<Self>`, passed as `fake`.
+        let actual_receiver = match sig.receiver() {
+            Some(receiver) if receiver.colon_token.is_some() => {
+                return Err(syn::Error::new(
+                    receiver.span(),
+                    "unsupported receiver type",
+                ));
+            }
+            Some(receiver) => {
+                let (fake_ty, actual) = match &receiver.reference {
+                    None => (
+                        quote! { ::implementation::Fake<Self> },
+                        quote! { ::implementation::Actual::into_actual(fake) },
+                    ),
+                    Some((and, lifetime)) if receiver.mutability.is_some() => (
+                        quote! { #and #lifetime mut ::implementation::Fake<Self> },
+                        quote! { ::implementation::Actual::actual_mut(fake) },
+                    ),
+                    Some((and, lifetime)) => (
+                        quote! { #and #lifetime ::implementation::Fake<Self> },
+                        quote! { ::implementation::Actual::actual(fake) },
+                    ),
+                };
+                *sig.inputs.first_mut().unwrap() = syn::parse_quote!(fake: #fake_ty);
+                Some(actual)
+            }
+            None => None,
+        };
+        let actual_args = actual_receiver
+            .into_iter()
+            .chain(args.iter().map(|arg| quote! { #arg }));
+
+        fake_trait_items.push(quote! {
+            #sig {
+                #fake_trait_actual::#ident(#(#actual_args),*) #dot_await
+            }
+        });
+    }
+
+    Ok(quote! {
+        #item_trait
+
+        #[doc = #fake_doc]
+        #vis trait #fake_ident #fake_trait_generics: Sized #fake_trait_where_clause {
+            #(#fake_trait_items)*
+        }
+
+        impl #impl_generics #trait_ident #trait_ty_generics for ::implementation::Fake<#t> #impl_where_clause {
+            #(#impl_items)*
+        }
+    })
+}
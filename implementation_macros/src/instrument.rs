@@ -0,0 +1,118 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::MethodOpts;
+use crate::util::contains_impl_trait;
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "#[instrument] takes no arguments",
+        ));
+    }
+    let mut item_impl: syn::ItemImpl = syn::parse2(input)?;
+
+    let trait_ident = match &item_impl.trait_ {
+        Some((None, path, _)) => path.segments.last().unwrap().ident.clone(),
+        _ => {
+            return Err(syn::Error::new(
+                item_impl.impl_token.span,
+                "#[instrument] must be applied to a trait impl block",
+            ))
+        }
+    };
+
+    for item in &mut item_impl.items {
+        if let syn::ImplItem::Fn(impl_fn) = item {
+            instrument_fn(&trait_ident, impl_fn)?;
+        }
+    }
+
+    Ok(quote! { #item_impl })
+}
+
+fn instrument_fn(trait_ident: &syn::Ident, impl_fn: &mut syn::ImplItemFn) -> syn::Result<()> {
+    let opts = MethodOpts::take(&mut impl_fn.attrs)?;
+    let sig = &impl_fn.sig;
+    let name = format!("{}::{}", trait_ident, sig.ident);
+
+    for record in &opts.record {
+        let is_arg = sig.inputs.iter().any(|input| match input {
+            syn::FnArg::Typed(pat_type) => {
+                matches!(pat_type.pat.as_ref(), syn::Pat::Ident(pat_ident) if &pat_ident.ident == record)
+            }
+            syn::FnArg::Receiver(_) => false,
+        });
+        if !is_arg {
+            return Err(syn::Error::new(
+                record.span(),
+                "record must name an argument of the method",
+            ));
+        }
+    }
+    let fields = opts
+        .record
+        .iter()
+        .map(|record| quote! { #record = ?#record });
+
+    let tracing = quote! { ::implementation::__private::tracing };
+    let span = quote! {
+        let __span = #tracing::info_span!(#name #(, #fields)*);
+    };
+    let block = &impl_fn.block;
+
+    // The output is checked for errors when it's a `Result`, which needs its type spelled out.
+    let result_ty = match &sig.output {
+        syn::ReturnType::Type(_, ty) if is_result(ty) && !contains_impl_trait(ty) => Some(ty),
+        _ => None,
+    };
+    let report_error = quote! {
+        if let ::core::result::Result::Err(error) = &__output {
+            #tracing::error!(error = ?error);
+        }
+    };
+
+    impl_fn.block = match (sig.asyncness, result_ty) {
+        (Some(_), Some(ty)) => syn::parse_quote!({
+            #span
+            let __output = #tracing::Instrument::instrument(
+                async move {
+                    let __output: #ty = #block;
+                    __output
+                },
+                ::core::clone::Clone::clone(&__span),
+            )
+            .await;
+            __span.in_scope(|| {
+                #report_error
+            });
+            __output
+        }),
+        (Some(_), None) => syn::parse_quote!({
+            #span
+            #tracing::Instrument::instrument(async move #block, __span).await
+        }),
+        (None, Some(ty)) => syn::parse_quote!({
+            #span
+            let __enter = __span.enter();
+            let __output = ::implementation::__private::call_once(move || {
+                let __output: #ty = #block;
+                __output
+            });
+            #report_error
+            __output
+        }),
+        (None, None) => syn::parse_quote!({
+            #span
+            let __enter = __span.enter();
+            #block
+        }),
+    };
+
+    Ok(())
+}
+
+fn is_result(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(type_path) if type_path.path.segments.last().is_some_and(|segment| segment.ident == "Result"))
+}
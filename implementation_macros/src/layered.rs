@@ -0,0 +1,209 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+
+use crate::util::{forward_assoc_item, fresh_type_param, reject_self, rename_args};
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "#[layered] takes no arguments",
+        ));
+    }
+    let item_trait: syn::ItemTrait = syn::parse2(input)?;
+    if let Some(attr) = item_trait.attrs.iter().find(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "send")
+    }) {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "#[layered] does not support #[send] traits, as a layer doesn't produce `Send` futures",
+        ));
+    }
+
+    let t = fresh_type_param(&item_trait.generics);
+    let l = {
+        let mut generics = item_trait.generics.clone();
+        generics.params.push(syn::parse_quote!(#t));
+        fresh_type_param(&generics)
+    };
+    let trait_ident = &item_trait.ident;
+    let (_, trait_ty_generics, _) = item_trait.generics.split_for_impl();
+    let actual_trait = quote! {
+        <::implementation::Impl<#t> as #trait_ident #trait_ty_generics>
+    };
+
+    let mut generics = item_trait.generics.clone();
+    generics.params.push(syn::parse_quote!(#l));
+    generics.params.push(syn::parse_quote!(#t));
+    let where_clause = generics.make_where_clause();
+    where_clause.predicates.push(syn::parse_quote! {
+        #l: ::implementation::Layer
+    });
+    where_clause.predicates.push(syn::parse_quote! {
+        ::implementation::Impl<#t>: #trait_ident #trait_ty_generics
+    });
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let items = item_trait
+        .items
+        .iter()
+        .map(|item| layered_item(item, &actual_trait))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #item_trait
+
+        impl #impl_generics #trait_ident #trait_ty_generics for ::implementation::Layered<#l, #t> #where_clause {
+            #(#items)*
+        }
+    })
+}
+
+fn layered_item(item: &syn::TraitItem, actual_trait: &TokenStream) -> syn::Result<TokenStream> {
+    match item {
+        syn::TraitItem::Fn(item_fn) => layered_fn(item_fn, actual_trait),
+        _ => forward_assoc_item(item, actual_trait)
+            .ok_or_else(|| syn::Error::new(item.span(), "unsupported trait item")),
+    }
+}
+
+fn layered_fn(item_fn: &syn::TraitItemFn, actual_trait: &TokenStream) -> syn::Result<TokenStream> {
+    reject_self(&item_fn.sig, "layered")?;
+    let mut sig = item_fn.sig.clone();
+    let dot_await = sig.asyncness.map(|_| quote! { .await });
+
+    let args = rename_args(&mut sig);
+    let ident = &sig.ident;
+    let method = ident.to_string();
+
+    let split = match sig.receiver() {
+        Some(receiver) if receiver.colon_token.is_none() => {
+            match (&receiver.reference, &receiver.mutability) {
+                (None, _) => quote! { ::implementation::Layered::into_parts(self) },
+                (Some(_), Some(_)) => quote! { ::implementation::Layered::__split_mut(self) },
+                (Some(_), None) => quote! { ::implementation::Layered::__split(self) },
+            }
+        }
+        Some(receiver) => {
+            return Err(syn::Error::new(
+                receiver.span(),
+                "unsupported receiver type",
+            ));
+        }
+        None => {
+            return Ok(quote! {
+                #sig {
+                    #actual_trait::#ident(#(#args),*) #dot_await
+                }
+            })
+        }
+    };
+
+    if sig.asyncness.is_some() {
+        // The future is created once, from clones of the arguments that the layer gets to see.
+        let indices = (0..args.len()).map(syn::Index::from);
+        return Ok(quote! {
+            #sig {
+                let (layer, actual) = #split;
+                let args = (#(#args,)*);
+                let future = #actual_trait::#ident(actual, #(::core::clone::Clone::clone(&args.#indices)),*);
+                ::implementation::Layer::around_async(&layer, #method, &args, future).await
+            }
+        });
+    }
+
+    // Only `async` methods have their futures wrapped by the layer, a returned future would escape it.
+    if let syn::ReturnType::Type(_, ty) = &sig.output {
+        if is_impl_future(ty) {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "#[layered] does not support methods returning `impl Future`, which includes `async fn` in #[send] traits",
+            ));
+        }
+    }
+
+    // A layer may make the call more than once, so it can't hand out a mutable borrow that outlives the call.
+    if let Some(receiver) = sig.receiver() {
+        if receiver.reference.is_some()
+            && receiver.mutability.is_some()
+            && borrows_receiver(&sig.output, receiver)
+        {
+            return Err(syn::Error::new_spanned(
+                &sig.output,
+                "#[layered] does not support returning borrows of a `&mut self` receiver",
+            ));
+        }
+    }
+
+    // A layer may make the call more than once, but a receiver taken by value can only be handed out once.
+    let (take_actual, actual) = match sig.receiver() {
+        Some(receiver) if receiver.reference.is_none() => (
+            Some(quote! { let mut actual = ::core::option::Option::Some(actual); }),
+            quote! {
+                ::core::option::Option::take(&mut actual)
+                    .expect("a layer called a method taking `self` more than once")
+            },
+        ),
+        _ => (None, quote! { actual }),
+    };
+
+    Ok(quote! {
+        #sig {
+            let (layer, actual) = #split;
+            #take_actual
+            ::implementation::Layer::around(&layer, #method, (#(#args,)*), |(#(#args,)*)| {
+                #actual_trait::#ident(#actual, #(#args),*)
+            })
+        }
+    })
+}
+
+/// Whether the return type has an elided lifetime, or the lifetime of the receiver.
+/// Elided lifetimes that are hidden in a path, like `Ref<T>` for `Ref<'_, T>`, are not detected.
+fn borrows_receiver(output: &syn::ReturnType, receiver: &syn::Receiver) -> bool {
+    fn scan(stream: TokenStream, receiver_lifetime: Option<&syn::Ident>) -> bool {
+        let mut tokens = stream.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Punct(punct) if punct.as_char() == '&' => match tokens.peek() {
+                    Some(TokenTree::Punct(next)) if next.as_char() == '\'' => {}
+                    _ => return true,
+                },
+                TokenTree::Punct(punct) if punct.as_char() == '\'' => match tokens.next() {
+                    Some(TokenTree::Ident(ident))
+                        if ident == "_" || Some(&ident) == receiver_lifetime =>
+                    {
+                        return true
+                    }
+                    _ => {}
+                },
+                TokenTree::Group(group) if scan(group.stream(), receiver_lifetime) => return true,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    let receiver_lifetime = receiver
+        .reference
+        .as_ref()
+        .and_then(|(_, lifetime)| lifetime.as_ref())
+        .map(|lifetime| &lifetime.ident);
+    match output {
+        syn::ReturnType::Type(_, ty) => scan(ty.to_token_stream(), receiver_lifetime),
+        syn::ReturnType::Default => false,
+    }
+}
+
+fn is_impl_future(ty: &syn::Type) -> bool {
+    let syn::Type::ImplTrait(impl_trait) = ty else {
+        return false;
+    };
+    impl_trait.bounds.iter().any(|bound| {
+        matches!(bound, syn::TypeParamBound::Trait(bound) if bound.path.segments.last().is_some_and(|segment| segment.ident == "Future"))
+    })
+}
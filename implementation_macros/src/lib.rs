@@ -0,0 +1,993 @@
+//! Procedural macros for the [implementation](https://docs.rs/implementation) crate.
+//!
+//! These macros are re-exported from `implementation` when its `macros` feature is enabled,
+//! and should be used through that crate.
+
+#![forbid(unsafe_code)]
+
+use proc_macro::TokenStream;
+
+mod accessors;
+mod actual;
+mod attr;
+mod context;
+mod dispatch;
+mod dyn_;
+mod fake;
+mod instrument;
+mod layered;
+mod project;
+mod provide;
+mod send;
+mod spy;
+mod trait_;
+mod unmock;
+mod util;
+
+/// Write the actual implementation of a trait, targeting [Impl](https://docs.rs/implementation/latest/implementation/struct.Impl.html).
+///
+/// The attribute is applied to a trait impl block for `Impl`, and turns it into a generic
+/// `impl<T> Trait for Impl<T>`. The `where Impl<T>: ...` bounds are inferred from the dependency
+/// traits that get called on `self`.
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// # struct Website;
+/// trait ScrapeTheInternet {
+///     fn scrape_the_internet(&self) -> Vec<Website>;
+/// }
+///
+/// trait GetMaxNumberOfPages {
+///     fn get_max_number_of_pages(&self) -> Option<usize>;
+/// }
+///
+/// #[implementation::actual]
+/// impl ScrapeTheInternet for Impl {
+///     fn scrape_the_internet(&self) -> Vec<Website> {
+///         let max_number_of_pages = GetMaxNumberOfPages::get_max_number_of_pages(self);
+///         vec![]
+///     }
+/// }
+///
+/// struct Config;
+///
+/// impl GetMaxNumberOfPages for Impl<Config> {
+///     fn get_max_number_of_pages(&self) -> Option<usize> {
+///         Some(42)
+///     }
+/// }
+///
+/// let websites = Impl::new(Config).scrape_the_internet();
+/// ```
+///
+/// The above expands to:
+///
+/// ```rust
+/// # use implementation::Impl;
+/// # struct Website;
+/// # trait ScrapeTheInternet { fn scrape_the_internet(&self) -> Vec<Website>; }
+/// # trait GetMaxNumberOfPages { fn get_max_number_of_pages(&self) -> Option<usize>; }
+/// impl<T> ScrapeTheInternet for Impl<T>
+///     where Impl<T>: GetMaxNumberOfPages
+/// {
+///     fn scrape_the_internet(&self) -> Vec<Website> {
+///         let max_number_of_pages = GetMaxNumberOfPages::get_max_number_of_pages(self);
+///         vec![]
+///     }
+/// }
+/// ```
+///
+/// # Dependency inference
+/// A procedural macro has no type information, so a plain method call like `self.get_max_number_of_pages()`
+/// can't be traced back to its trait. Dependencies are instead recognized when called using
+/// fully qualified syntax:
+///
+/// * `Dep::method(self, ...)`
+/// * `<Self as Dep>::method(...)`
+///
+/// `Dep` is told apart from a module by its UpperCamelCase name, so free functions taking `self`,
+/// like `helpers::log(self)` or `std::mem::drop(self)`, don't become dependencies:
+///
+/// ```rust
+/// # use implementation::Impl;
+/// mod helpers {
+///     pub fn log<T>(_: &T) {}
+/// }
+///
+/// trait Greet {
+///     fn greet(&self) -> String;
+/// }
+///
+/// #[implementation::actual]
+/// impl Greet for Impl {
+///     fn greet(&self) -> String {
+///         helpers::log(self);
+///         "Hello".to_string()
+///     }
+/// }
+///
+/// assert_eq!(Impl::new(()).greet(), "Hello");
+/// ```
+///
+/// Dependencies that are not called this way can be listed explicitly: `#[implementation::actual(Dep1, Dep2)]`.
+///
+/// # Generics
+/// The self type may also be written as `Impl<T>`, in which case the generic parameters of the impl
+/// block are kept as written. This is useful for adding bounds that are not inferred:
+///
+/// ```rust
+/// # use implementation::Impl;
+/// trait Describe {
+///     fn describe(&self) -> String;
+/// }
+///
+/// #[implementation::actual]
+/// impl<T: std::fmt::Debug> Describe for Impl<T> {
+///     fn describe(&self) -> String {
+///         format!("{:?}", self.as_ref())
+///     }
+/// }
+///
+/// assert_eq!(Impl::new(42).describe(), "42");
+/// ```
+#[proc_macro_attribute]
+pub fn actual(attr: TokenStream, input: TokenStream) -> TokenStream {
+    output(actual::expand(attr.into(), input.into()))
+}
+
+/// Derive one getter trait per field of a config struct, implemented for `Impl<Config>`.
+///
+/// For a field `max_number_of_pages: Option<usize>`, the derive generates:
+///
+/// ```rust
+/// # struct Config { max_number_of_pages: Option<usize> }
+/// use implementation::{Impl, Project};
+///
+/// trait GetMaxNumberOfPages {
+///     fn get_max_number_of_pages(&self) -> &Option<usize>;
+/// }
+///
+/// impl<T> GetMaxNumberOfPages for Impl<T>
+///     where Impl<T>: Project<Config>
+/// {
+///     fn get_max_number_of_pages(&self) -> &Option<usize> {
+///         &<Self as Project<Config>>::project(self).max_number_of_pages
+///     }
+/// }
+/// ```
+///
+/// The traits get the same visibility as the struct. Because the accessors are implemented through
+/// [Project](https://docs.rs/implementation/latest/implementation/trait.Project.html),
+/// they are also available for any `Impl` that delegates to `Impl<Config>`. Actual implementations can then depend on
+/// individual config values:
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// #[derive(implementation::Accessors)]
+/// struct Config {
+///     max_number_of_pages: Option<usize>,
+///     #[implementation(skip)]
+///     secret: String,
+/// }
+///
+/// trait CountPages {
+///     fn count_pages(&self) -> usize;
+/// }
+///
+/// #[implementation::actual(GetMaxNumberOfPages)]
+/// impl CountPages for Impl {
+///     fn count_pages(&self) -> usize {
+///         self.get_max_number_of_pages().unwrap_or(0)
+///     }
+/// }
+///
+/// let config = Impl::new(Config {
+///     max_number_of_pages: Some(42),
+///     secret: "hunter2".to_string(),
+/// });
+/// assert_eq!(config.count_pages(), 42);
+/// ```
+///
+/// The names of the traits only depend on the field names, so two structs with a field of the same name in one module
+/// generate conflicting traits. `#[implementation(rename = "...")]` gives the accessor another name:
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// #[derive(implementation::Accessors)]
+/// struct DbConfig {
+///     #[implementation(rename = "db_url")]
+///     url: String,
+/// }
+///
+/// #[derive(implementation::Accessors)]
+/// struct HttpConfig {
+///     url: String,
+/// }
+///
+/// let db = Impl::new(DbConfig { url: "postgres://".to_string() });
+/// let http = Impl::new(HttpConfig { url: "https://".to_string() });
+/// assert_eq!((db.get_db_url().as_str(), http.get_url().as_str()), ("postgres://", "https://"));
+/// ```
+///
+/// # Field options
+/// * `#[implementation(skip)]`: Don't generate an accessor for this field.
+/// * `#[implementation(rename = "name")]`: Name the accessor `GetName::get_name` instead of after the field.
+/// * `#[implementation(delegate)]`: Used by [macro@Project].
+/// * `#[implementation(provide)]`: Used by [macro@Provide].
+#[proc_macro_derive(Accessors, attributes(implementation))]
+pub fn derive_accessors(input: TokenStream) -> TokenStream {
+    output(accessors::expand(syn::parse_macro_input!(input)))
+}
+
+/// Derive [Project](https://docs.rs/implementation/latest/implementation/trait.Project.html)
+/// for every field annotated with `#[implementation(delegate)]`.
+///
+/// A delegated field must have the type `Impl<Sub>`, and makes the `Impl` of the struct project into it.
+/// Trait bounds on `Impl<MyConfig>` written in terms of `Project<Sub>` are then resolved by the nested field:
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// #[derive(implementation::Project)]
+/// struct MyConfig {
+///     param1: i32,
+///     #[implementation(delegate)]
+///     sub_config: Impl<SubConfig>,
+/// }
+///
+/// #[derive(implementation::Accessors)]
+/// struct SubConfig {
+///     param2: i32,
+/// }
+///
+/// let my_config = Impl::new(MyConfig {
+///     param1: 1,
+///     sub_config: Impl::new(SubConfig { param2: 2 }),
+/// });
+/// assert_eq!(*my_config.get_param2(), 2);
+/// ```
+///
+/// Delegation is not transitive: each level of nesting that should be reachable from the root needs its own delegated field.
+#[proc_macro_derive(Project, attributes(implementation))]
+pub fn derive_project(input: TokenStream) -> TokenStream {
+    output(project::expand(syn::parse_macro_input!(input)))
+}
+
+/// Compose a context from independent values, by making the `Impl` of a tuple project into each of its elements.
+///
+/// The attribute is applied to a type alias of a tuple, and implements
+/// [Project](https://docs.rs/implementation/latest/implementation/trait.Project.html)`<A>` for `Impl<(A, B, ..)>`
+/// for every element type. Traits implemented in terms of `Project<A>`, like those derived by [macro@Accessors],
+/// are then available for the `Impl` of the tuple, and so are bounds written in terms of those traits:
+///
+/// ```rust
+/// use implementation::{Impl, Project};
+///
+/// #[derive(implementation::Accessors)]
+/// struct DbConfig {
+///     url: &'static str,
+/// }
+///
+/// struct HttpConfig {
+///     port: u16,
+/// }
+///
+/// trait GetPort {
+///     fn get_port(&self) -> u16;
+/// }
+///
+/// impl<T> GetPort for Impl<T>
+///     where Impl<T>: Project<HttpConfig>
+/// {
+///     fn get_port(&self) -> u16 {
+///         Project::<HttpConfig>::project(self).port
+///     }
+/// }
+///
+/// fn endpoint<T>(context: &Impl<T>) -> String
+///     where Impl<T>: GetUrl + GetPort
+/// {
+///     format!("{}:{}", context.get_url(), context.get_port())
+/// }
+///
+/// #[implementation::context]
+/// type AppContext = (DbConfig, HttpConfig);
+///
+/// let context: Impl<AppContext> = Impl::new((DbConfig { url: "postgres://" }, HttpConfig { port: 5432 }));
+/// assert_eq!(endpoint(&context), "postgres://:5432");
+/// ```
+///
+/// Each type can only appear once in a context, so that every projection is unambiguous:
+///
+/// ```rust,compile_fail
+/// struct Port(u16);
+///
+/// #[implementation::context]
+/// type Ambiguous = (Port, Port);
+/// ```
+///
+/// The element types must be concrete, and at least one of them local to the crate, to satisfy the orphan rule.
+#[proc_macro_attribute]
+pub fn context(attr: TokenStream, input: TokenStream) -> TokenStream {
+    output(context::expand(attr.into(), input.into()))
+}
+
+/// Derive [Provide](https://docs.rs/implementation/latest/implementation/trait.Provide.html)
+/// for every field annotated with `#[implementation(provide)]`.
+///
+/// Each provided field makes its type available as a dependency through
+/// [Get](https://docs.rs/implementation/latest/implementation/trait.Get.html):
+///
+/// ```rust
+/// use implementation::{Get, Impl};
+///
+/// struct HttpClient;
+/// struct Database;
+///
+/// #[derive(implementation::Provide)]
+/// struct App {
+///     #[implementation(provide)]
+///     http_client: HttpClient,
+///     #[implementation(provide)]
+///     database: Database,
+///     name: String,
+/// }
+///
+/// trait Serve {
+///     fn serve(&self);
+/// }
+///
+/// #[implementation::actual]
+/// impl Serve for Impl {
+///     fn serve(&self) {
+///         let http_client = Get::<HttpClient>::get(self);
+///         let database = Get::<Database>::get(self);
+///     }
+/// }
+///
+/// Impl::new(App {
+///     http_client: HttpClient,
+///     database: Database,
+///     name: "app".to_string(),
+/// })
+/// .serve();
+/// ```
+///
+/// Each provided field must have a distinct type.
+#[proc_macro_derive(Provide, attributes(implementation))]
+pub fn derive_provide(input: TokenStream) -> TokenStream {
+    output(provide::expand(syn::parse_macro_input!(input)))
+}
+
+/// Generate an implementation of the trait for [Spy](https://docs.rs/implementation/latest/implementation/struct.Spy.html),
+/// which records each call before forwarding it to the actual implementation.
+///
+/// Requires the `alloc` feature of `implementation`.
+///
+/// ```rust
+/// use implementation::{Impl, Spy};
+///
+/// #[implementation::spy]
+/// trait Greet {
+///     fn greet(&self, name: &str) -> String;
+/// }
+///
+/// impl<T> Greet for Impl<T> {
+///     fn greet(&self, name: &str) -> String {
+///         format!("Hello, {name}!")
+///     }
+/// }
+///
+/// let spy = Spy::new(());
+/// assert_eq!(spy.greet("world"), "Hello, world!");
+///
+/// let calls = spy.calls();
+/// assert_eq!(calls[0].method(), "greet");
+/// assert_eq!(calls[0].args(), ["\"world\""]);
+/// assert_eq!(calls[0].output(), "\"Hello, world!\"");
+/// ```
+///
+/// The generated implementation is:
+///
+/// ```rust
+/// # use implementation::{Impl, Spy};
+/// # trait Greet { fn greet(&self, name: &str) -> String; }
+/// impl<T> Greet for Spy<T>
+///     where Impl<T>: Greet
+/// {
+///     fn greet(&self, arg0: &str) -> String {
+///         // record the call and forward to `<Impl<T> as Greet>::greet`
+/// #       todo!()
+///     }
+/// }
+/// ```
+///
+/// All arguments and return values must implement `Debug`. Async methods are supported, with the output
+/// recorded when the future completes. Since `Spy` is not `Sync`, it can't implement traits that require `Send` futures.
+///
+/// Methods with `Self` in their argument or return types are not supported, as the actual implementation
+/// would take or return an `Impl<T>` where a `Spy<T>` is expected:
+///
+/// ```rust,compile_fail
+/// #[implementation::spy]
+/// trait Duplicate {
+///     fn duplicate(&self) -> Self
+///     where
+///         Self: Sized;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn spy(attr: TokenStream, input: TokenStream) -> TokenStream {
+    output(spy::expand(attr.into(), input.into()))
+}
+
+/// Require the futures returned from the `async fn`s of a trait to be `Send`.
+///
+/// An `async fn` declared in a trait doesn't say whether its future is `Send`, which makes it unusable from
+/// multi-threaded executors in generic code. This attribute rewrites each `async fn f(..) -> R` of the trait
+/// into `fn f(..) -> impl Future<Output = R> + Send`.
+///
+/// Implementations can still be written using `async fn`. The actual implementation for `Impl<T>` typically
+/// needs `T: Sync` for its futures to be `Send`:
+///
+/// ```rust
+/// use implementation::Impl;
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     struct NoopWaker;
+/// #     impl std::task::Wake for NoopWaker {
+/// #         fn wake(self: std::sync::Arc<Self>) {}
+/// #     }
+/// #     let waker = std::task::Waker::from(std::sync::Arc::new(NoopWaker));
+/// #     let mut cx = std::task::Context::from_waker(&waker);
+/// #     let mut future = std::pin::pin!(future);
+/// #     loop {
+/// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+///
+/// #[implementation::send]
+/// trait ScrapeTheInternet {
+///     async fn scrape_the_internet(&self) -> Vec<String>;
+/// }
+///
+/// #[implementation::send]
+/// trait GetMaxNumberOfPages {
+///     async fn get_max_number_of_pages(&self) -> usize;
+/// }
+///
+/// #[implementation::actual]
+/// impl<T: Sync> ScrapeTheInternet for Impl<T> {
+///     async fn scrape_the_internet(&self) -> Vec<String> {
+///         let max_number_of_pages = GetMaxNumberOfPages::get_max_number_of_pages(self).await;
+///         vec![String::new(); max_number_of_pages]
+///     }
+/// }
+///
+/// struct Config;
+///
+/// impl GetMaxNumberOfPages for Impl<Config> {
+///     async fn get_max_number_of_pages(&self) -> usize {
+///         2
+///     }
+/// }
+///
+/// fn assert_send<F: Send>(future: F) -> F {
+///     future
+/// }
+///
+/// let config = Impl::new(Config);
+/// let websites = block_on(assert_send(config.scrape_the_internet()));
+/// assert_eq!(websites.len(), 2);
+/// ```
+#[proc_macro_attribute]
+pub fn send(attr: TokenStream, input: TokenStream) -> TokenStream {
+    output(send::expand(attr.into(), input.into()))
+}
+
+/// Mark a trait as having one actual implementation, written generically for `Impl<T>`.
+///
+/// The attribute is applied to both the trait and its actual implementation, which must be written for `Impl<T>`,
+/// generic over `T`. Any other implementation for an `Impl` is then a compile error, even one for a concrete
+/// `Impl<Config>` that the bounds of the actual implementation leave uncovered. Fakes are implemented for
+/// [Fake](https://docs.rs/implementation/latest/implementation/struct.Fake.html) instead, or generated with the
+/// `#[fake]`, `#[spy]`, `#[layered]`, `#[dispatch]`, `#[dyn_]` and `#[unimock]` attributes:
+///
+/// ```rust
+/// use implementation::{Fake, Impl};
+///
+/// #[implementation::trait_]
+/// trait GetUrl {
+///     fn get_url(&self) -> String;
+/// }
+///
+/// trait GetHost {
+///     fn get_host(&self) -> String;
+/// }
+///
+/// #[implementation::actual]
+/// #[implementation::trait_]
+/// impl GetUrl for Impl {
+///     fn get_url(&self) -> String {
+///         let host = GetHost::get_host(self);
+///         format!("https://{host}")
+///     }
+/// }
+///
+/// impl GetUrl for Fake<()> {
+///     fn get_url(&self) -> String {
+///         String::from("https://example.com")
+///     }
+/// }
+///
+/// impl GetHost for Impl<&'static str> {
+///     fn get_host(&self) -> String {
+///         self.to_string()
+///     }
+/// }
+///
+/// assert_eq!(Impl::new("localhost").get_url(), "https://localhost");
+/// assert_eq!(Fake::new(()).get_url(), "https://example.com");
+/// ```
+///
+/// `#[actual]` must be placed above `#[trait_]`, so that the bounds it adds are seen. An implementation for an `Impl`
+/// that is not the marked actual implementation is rejected:
+///
+/// ```rust,compile_fail,E0277
+/// # use implementation::Impl;
+/// # #[implementation::trait_]
+/// # trait GetUrl {
+/// #     fn get_url(&self) -> String;
+/// # }
+/// # trait GetHost {
+/// #     fn get_host(&self) -> String;
+/// # }
+/// # #[implementation::actual]
+/// # #[implementation::trait_]
+/// # impl GetUrl for Impl {
+/// #     fn get_url(&self) -> String {
+/// #         let host = GetHost::get_host(self);
+/// #         format!("https://{host}")
+/// #     }
+/// # }
+/// struct Config;
+///
+/// impl GetUrl for Impl<Config> {
+///     fn get_url(&self) -> String {
+///         String::from("https://example.com")
+///     }
+/// }
+/// ```
+///
+/// When used with unimock, `#[trait_]` must be placed above `#[unimock]`. The marked implementation must name the trait
+/// by its own name, not through a renaming import.
+#[proc_macro_attribute]
+pub fn trait_(attr: TokenStream, input: TokenStream) -> TokenStream {
+    output(trait_::expand(attr.into(), input.into()))
+}
+
+/// Generate a fake implementation of the trait for [Fake](https://docs.rs/implementation/latest/implementation/struct.Fake.html),
+/// where every method not overridden delegates to the actual implementation.
+///
+/// For a trait `Store`, the attribute generates a companion trait `StoreFake`, to be implemented for fake scenario types.
+/// It has the same methods as `Store`, with the receiver replaced by a `fake` parameter of type `Fake<Self>`, and each
+/// having a default body calling the actual implementation for `Impl<Self>`. `Fake<T>` implements `Store` by delegating
+/// to `T`'s `StoreFake`, so a partial fake only needs to override the methods it cares about:
+///
+/// ```rust
+/// use implementation::{call_actual, Fake, Impl};
+///
+/// #[implementation::fake]
+/// trait Store {
+///     fn get(&self, key: &str) -> Option<String>;
+///     fn put(&self, key: &str, value: String);
+/// }
+///
+/// impl<T> Store for Impl<T> {
+///     fn get(&self, key: &str) -> Option<String> {
+///         Some(format!("value of {key}"))
+///     }
+///
+///     fn put(&self, key: &str, value: String) {
+///         panic!("no writes allowed");
+///     }
+/// }
+///
+/// struct ReadOnly;
+///
+/// impl StoreFake for ReadOnly {
+///     fn put(fake: &Fake<Self>, key: &str, value: String) {}
+/// }
+///
+/// struct Uppercase;
+///
+/// impl StoreFake for Uppercase {
+///     fn get(fake: &Fake<Self>, key: &str) -> Option<String> {
+///         call_actual!(fake.get(key)).map(|value| value.to_uppercase())
+///     }
+/// }
+///
+/// let read_only = Fake::new(ReadOnly);
+/// read_only.put("key", "value".to_string());
+/// assert_eq!(read_only.get("key").unwrap(), "value of key");
+///
+/// assert_eq!(Fake::new(Uppercase).get("key").unwrap(), "VALUE OF KEY");
+/// ```
+///
+/// Associated types and consts of `Fake<T>` are always those of the actual implementation.
+/// Methods with `Self` in their argument or return types are not supported:
+///
+/// ```rust,compile_fail
+/// #[implementation::fake]
+/// trait Duplicate {
+///     fn duplicate(&self) -> Self
+///     where
+///         Self: Sized;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn fake(attr: TokenStream, input: TokenStream) -> TokenStream {
+    output(fake::expand(attr.into(), input.into()))
+}
+
+/// Generate an object-safe companion of the trait, so that actual implementations can be stored as trait objects.
+///
+/// For a trait `ScrapeTheInternet`, the attribute generates a `DynScrapeTheInternet` trait, with the methods
+/// that can be called through a `dyn`, and implements it for every `Impl<T>` that implements `ScrapeTheInternet`.
+/// A method taking `self` by value takes `self: Box<Self>` in the companion trait. Methods that are generic,
+/// `async`, lack a receiver, or mention `Self` or `impl Trait` in their arguments or return type are left out:
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// #[implementation::dyn_]
+/// trait ScrapeTheInternet {
+///     fn scrape_the_internet(&self) -> Vec<String>;
+///     fn with_limit<L: Into<usize>>(self, limit: L) -> Self where Self: Sized;
+/// }
+///
+/// struct Config {
+///     url: &'static str,
+/// }
+///
+/// impl ScrapeTheInternet for Impl<Config> {
+///     fn scrape_the_internet(&self) -> Vec<String> {
+///         vec![format!("<html>{}</html>", self.url)]
+///     }
+///
+///     fn with_limit<L: Into<usize>>(self, limit: L) -> Self {
+///         self
+///     }
+/// }
+///
+/// impl ScrapeTheInternet for Impl<&'static str> {
+///     fn scrape_the_internet(&self) -> Vec<String> {
+///         vec![self.to_string()]
+///     }
+///
+///     fn with_limit<L: Into<usize>>(self, limit: L) -> Self {
+///         self
+///     }
+/// }
+///
+/// let scrapers: Vec<Box<dyn DynScrapeTheInternet>> = vec![
+///     Box::new(Impl::new(Config { url: "https://example.com" })),
+///     Box::new(Impl::new("cached")),
+/// ];
+///
+/// let pages: Vec<String> = scrapers.iter().flat_map(|scraper| scraper.scrape_the_internet()).collect();
+/// assert_eq!(pages, ["<html>https://example.com</html>", "cached"]);
+/// ```
+///
+/// When every item of the trait has an object-safe counterpart, the trait is also implemented for `Box<D>`
+/// where `D: DynScrapeTheInternet + ?Sized`, so that a boxed trait object can be passed back into generic code:
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// #[implementation::dyn_]
+/// trait Greet {
+///     fn greet(&self, name: &str) -> String;
+/// }
+///
+/// impl<T> Greet for Impl<T> {
+///     fn greet(&self, name: &str) -> String {
+///         format!("Hello, {name}!")
+///     }
+/// }
+///
+/// fn greet_world(greeter: &impl Greet) -> String {
+///     greeter.greet("world")
+/// }
+///
+/// let greeter: Box<dyn DynGreet + Send + Sync> = Box::new(Impl::new(()));
+/// assert_eq!(greet_world(&greeter), "Hello, world!");
+/// ```
+///
+/// Auto traits and lifetimes among the supertraits of the trait are also supertraits of the companion. Other
+/// supertraits, like `Clone`, could make the companion unusable as a trait object, and are left out. Since `Box<D>`
+/// only inherits auto traits and lifetimes from `D`, the trait is implemented for `Box<D>` only when its
+/// supertraits are among `Send`, `Sync`, `Unpin`, `UnwindSafe`, `RefUnwindSafe`, `Sized` and lifetimes:
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// #[implementation::dyn_]
+/// trait Named: Send {
+///     fn name(&self) -> String;
+/// }
+///
+/// impl Named for Impl<&'static str> {
+///     fn name(&self) -> String {
+///         self.to_string()
+///     }
+/// }
+///
+/// fn name_in_thread(named: impl Named + 'static) -> String {
+///     std::thread::spawn(move || named.name()).join().unwrap()
+/// }
+///
+/// let named: Box<dyn DynNamed> = Box::new(Impl::new("ferris"));
+/// assert_eq!(name_in_thread(named), "ferris");
+/// ```
+///
+/// A trait with a `Clone` supertrait still has a companion that can be used as a trait object:
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// #[implementation::dyn_]
+/// trait Port: Clone {
+///     fn port(&self) -> u16;
+/// }
+///
+/// impl Port for Impl<u16> {
+///     fn port(&self) -> u16 {
+///         **self
+///     }
+/// }
+///
+/// let port: Box<dyn DynPort> = Box::new(Impl::new(5432));
+/// assert_eq!(port.port(), 5432);
+/// ```
+///
+/// The generated code requires the `alloc` feature of the implementation crate.
+#[proc_macro_attribute]
+pub fn dyn_(attr: TokenStream, input: TokenStream) -> TokenStream {
+    output(dyn_::expand(attr.into(), input.into()))
+}
+
+/// Implement the trait for [Dispatch](https://docs.rs/implementation/latest/implementation/enum.Dispatch.html),
+/// dispatching each method to the selected actual or fake implementation.
+///
+/// `Dispatch<A, F>` implements the trait when both `A` and `F` do:
+///
+/// ```rust
+/// use implementation::{Fake, Impl, Dispatch};
+///
+/// #[implementation::dispatch]
+/// trait Storage {
+///     type Key;
+///
+///     fn put(&mut self, value: String) -> Self::Key;
+///     async fn flush(&self) -> usize;
+/// }
+///
+/// impl<T> Storage for Impl<T> {
+///     type Key = u64;
+///
+///     fn put(&mut self, value: String) -> u64 {
+///         42
+///     }
+///
+///     async fn flush(&self) -> usize {
+///         1
+///     }
+/// }
+///
+/// impl Storage for Fake<()> {
+///     type Key = u64;
+///
+///     fn put(&mut self, value: String) -> u64 {
+///         0
+///     }
+///
+///     async fn flush(&self) -> usize {
+///         0
+///     }
+/// }
+///
+/// let mut storage: Dispatch<Impl<()>, Fake<()>> = Dispatch::Actual(Impl::new(()));
+/// assert_eq!(storage.put("value".to_string()), 42);
+/// ```
+///
+/// Associated types must be the same for both implementations, and associated consts are those of the actual implementation.
+/// Every method needs a receiver, and may not return `impl Trait`, as the two implementations would return different types:
+///
+/// ```rust,compile_fail
+/// #[implementation::dispatch]
+/// trait Numbers {
+///     fn numbers(&self) -> impl Iterator<Item = u32>;
+/// }
+/// ```
+///
+/// Methods with `Self` in their argument or return types are not supported either:
+///
+/// ```rust,compile_fail
+/// #[implementation::dispatch]
+/// trait Duplicate {
+///     fn duplicate(&self) -> Self
+///     where
+///         Self: Sized;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn dispatch(attr: TokenStream, input: TokenStream) -> TokenStream {
+    output(dispatch::expand(attr.into(), input.into()))
+}
+
+/// Instrument every method of an actual implementation with a [tracing](https://docs.rs/tracing) span (requires the `tracing` feature).
+///
+/// Each span is named after the trait and the method, as in `ScrapeTheInternet::scrape_the_internet`, and is entered for the
+/// duration of the call, including across `.await` points of `async` methods. Arguments are recorded as `Debug` fields
+/// when selected with `#[implementation(record(...))]`. A method returning a `Result` emits an error event when it returns `Err`:
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// trait ScrapeTheInternet {
+///     fn scrape_the_internet(&self, max_pages: usize) -> Result<Vec<String>, String>;
+/// }
+///
+/// #[implementation::instrument]
+/// impl<T> ScrapeTheInternet for Impl<T> {
+///     #[implementation(record(max_pages))]
+///     fn scrape_the_internet(&self, max_pages: usize) -> Result<Vec<String>, String> {
+///         if max_pages == 0 {
+///             return Err("no pages to scrape".to_string());
+///         }
+///         Ok(vec!["<html></html>".to_string()])
+///     }
+/// }
+///
+/// assert!(Impl::new(()).scrape_the_internet(0).is_err());
+/// ```
+///
+/// The attribute works on any trait impl block, so it may also be combined with `#[actual]`.
+#[proc_macro_attribute]
+pub fn instrument(attr: TokenStream, input: TokenStream) -> TokenStream {
+    output(instrument::expand(attr.into(), input.into()))
+}
+
+/// Implement the trait for [Layered](https://docs.rs/implementation/latest/implementation/struct.Layered.html),
+/// passing each call to the actual implementation through a [Layer](https://docs.rs/implementation/latest/implementation/trait.Layer.html).
+///
+/// `Layered<L, T>` implements the trait when `Impl<T>` does, so cross-cutting behaviour can be added without touching the actual implementation:
+///
+/// ```rust
+/// use core::cell::RefCell;
+/// use core::fmt::Debug;
+/// use core::future::Future;
+/// use implementation::{Impl, Layer, Layered};
+///
+/// #[implementation::layered]
+/// trait Greet {
+///     fn greet(&self, name: &str) -> String;
+/// }
+///
+/// impl<T> Greet for Impl<T> {
+///     fn greet(&self, name: &str) -> String {
+///         format!("Hello, {name}!")
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct Log(RefCell<Vec<String>>);
+///
+/// impl Layer for Log {
+///     fn around<A: Debug + Clone, R>(&self, method: &'static str, args: A, mut call: impl FnMut(A) -> R) -> R {
+///         self.0.borrow_mut().push(format!("{method}{args:?}"));
+///         call(args)
+///     }
+///
+///     async fn around_async<A: Debug, F: Future>(&self, method: &'static str, args: &A, future: F) -> F::Output {
+///         self.0.borrow_mut().push(format!("{method}{args:?}"));
+///         future.await
+///     }
+/// }
+///
+/// let greeter = Layered::new(Log::default(), ());
+/// assert_eq!(greeter.greet("world"), "Hello, world!");
+/// assert_eq!(greeter.layer().0.take(), ["greet(\"world\",)"]);
+/// ```
+///
+/// The arguments of every method with a `self` receiver must implement `Debug`, and `Clone` so that the layer can make the call again.
+/// The arguments of `async` methods are cloned once, as the future returned by the actual implementation takes ownership of them.
+/// Methods returning a borrow of a `&mut self` receiver are not supported, as the layer may make the call more than once.
+/// Lifetimes elided in a path, like `Ref<T>` for `Ref<'_, T>`, are not detected, and fail to compile inside the generated code:
+///
+/// ```rust,compile_fail
+/// #[implementation::layered]
+/// trait Buffer {
+///     fn get_mut(&mut self) -> &mut String;
+/// }
+/// ```
+///
+/// A layer doesn't produce `Send` futures, so `#[layered]` can't be combined with [`#[send]`](macro@send), in either order,
+/// nor with methods returning `impl Future`, whose futures would escape the layer:
+///
+/// ```rust,compile_fail
+/// #[implementation::layered]
+/// #[implementation::send]
+/// trait ScrapeTheInternet {
+///     async fn scrape_the_internet(&self) -> Vec<String>;
+/// }
+/// ```
+///
+/// Methods with `Self` in their argument or return types are not supported either:
+///
+/// ```rust,compile_fail
+/// #[implementation::layered]
+/// trait Duplicate {
+///     fn duplicate(&self) -> Self
+///     where
+///         Self: Sized;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn layered(attr: TokenStream, input: TokenStream) -> TokenStream {
+    output(layered::expand(attr.into(), input.into()))
+}
+
+/// Route unmocked calls of a [unimock](https://docs.rs/unimock) trait to its actual implementation.
+///
+/// The attribute must be placed above `#[unimock]`, and fills in its `unmock_with` argument.
+/// Each method is unmocked by calling the actual implementation for `Impl<Unimock>`, so that calls it
+/// makes to other traits through `T` in turn pass through unimock:
+///
+/// ```rust
+/// use implementation::Impl;
+/// use unimock::{matching, unimock, MockFn, Unimock};
+///
+/// #[unimock(api = GetUsernameMock)]
+/// trait GetUsername {
+///     fn get_username(&self, id: u32) -> String;
+/// }
+///
+/// #[implementation::unmock]
+/// #[unimock(api = GreetMock)]
+/// trait Greet {
+///     fn greet(&self, id: u32) -> String;
+/// }
+///
+/// impl<T: GetUsername> Greet for Impl<T> {
+///     fn greet(&self, id: u32) -> String {
+///         format!("Hello, {}!", self.get_username(id))
+///     }
+/// }
+///
+/// let deps = Unimock::new_partial(
+///     GetUsernameMock::get_username
+///         .next_call(matching!(42))
+///         .returns("Alice".to_string()),
+/// );
+/// assert_eq!(deps.greet(42), "Hello, Alice!");
+/// ```
+///
+/// Methods taking `&mut self` or no receiver, or with non-identifier argument patterns, are not unmocked.
+/// For generic traits, the actual implementation must be at least as generic as the one unimock generates.
+#[proc_macro_attribute]
+pub fn unmock(attr: TokenStream, input: TokenStream) -> TokenStream {
+    output(unmock::expand(attr.into(), input.into()))
+}
+
+fn output(result: syn::Result<proc_macro2::TokenStream>) -> TokenStream {
+    match result {
+        Ok(stream) => stream.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,65 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::FieldOpts;
+
+pub fn expand(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Project can only be derived for structs",
+            ))
+        }
+    };
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut output = TokenStream::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        if !FieldOpts::parse(&field.attrs)?.delegate {
+            continue;
+        }
+
+        let sub_ty = impl_inner_ty(&field.ty).ok_or_else(|| {
+            syn::Error::new_spanned(&field.ty, "delegated fields must have the type `Impl<_>`")
+        })?;
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        };
+
+        output.extend(quote! {
+            impl #impl_generics ::implementation::Project<#sub_ty> for ::implementation::Impl<#ident #ty_generics> #where_clause {
+                fn project(&self) -> &::implementation::Impl<#sub_ty> {
+                    &self.#member
+                }
+            }
+        });
+    }
+
+    Ok(output)
+}
+
+/// Extract `T` from `Impl<T>`.
+fn impl_inner_ty(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    if type_path.qself.is_some() {
+        return None;
+    }
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Impl" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(syn::GenericArgument::Type(inner)) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
@@ -0,0 +1,42 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::attr::FieldOpts;
+
+pub fn expand(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Provide can only be derived for structs",
+            ))
+        }
+    };
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut output = TokenStream::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        if !FieldOpts::parse(&field.attrs)?.provide {
+            continue;
+        }
+
+        let field_ty = &field.ty;
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        };
+
+        output.extend(quote! {
+            impl #impl_generics ::implementation::Provide<#field_ty> for #ident #ty_generics #where_clause {
+                fn provide(&self) -> &#field_ty {
+                    &self.#member
+                }
+            }
+        });
+    }
+
+    Ok(output)
+}
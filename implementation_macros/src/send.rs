@@ -0,0 +1,41 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(attr, "#[send] takes no arguments"));
+    }
+    let mut item_trait: syn::ItemTrait = syn::parse2(input)?;
+
+    for item in &mut item_trait.items {
+        if let syn::TraitItem::Fn(item_fn) = item {
+            if item_fn.sig.asyncness.is_some() {
+                desugar_async_fn(item_fn);
+            }
+        }
+    }
+
+    Ok(quote! { #item_trait })
+}
+
+/// Turn `async fn f() -> R` into `fn f() -> impl Future<Output = R> + Send`.
+fn desugar_async_fn(item_fn: &mut syn::TraitItemFn) {
+    let sig = &mut item_fn.sig;
+    sig.asyncness = None;
+
+    let output_ty = match &sig.output {
+        syn::ReturnType::Default => quote! { () },
+        syn::ReturnType::Type(_, ty) => quote! { #ty },
+    };
+    sig.output = syn::parse_quote! {
+        -> impl ::core::future::Future<Output = #output_ty> + ::core::marker::Send
+    };
+
+    if let Some(block) = &mut item_fn.default {
+        *block = syn::parse_quote! {
+            {
+                async move #block
+            }
+        };
+    }
+}
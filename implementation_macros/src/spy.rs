@@ -0,0 +1,107 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+
+use crate::util::{
+    contains_impl_trait, forward_assoc_item, fresh_type_param, reject_self, rename_args,
+};
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(attr, "#[spy] takes no arguments"));
+    }
+    let item_trait: syn::ItemTrait = syn::parse2(input)?;
+
+    let t = fresh_type_param(&item_trait.generics);
+    let trait_ident = &item_trait.ident;
+    let (_, trait_ty_generics, _) = item_trait.generics.split_for_impl();
+    let actual_trait = quote! {
+        <::implementation::Impl<#t> as #trait_ident #trait_ty_generics>
+    };
+
+    let mut generics = item_trait.generics.clone();
+    generics.params.push(syn::parse_quote!(#t));
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! {
+            ::implementation::Impl<#t>: #trait_ident #trait_ty_generics
+        });
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let items = item_trait
+        .items
+        .iter()
+        .map(|item| spy_item(item, &actual_trait))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #item_trait
+
+        impl #impl_generics #trait_ident #trait_ty_generics for ::implementation::Spy<#t> #where_clause {
+            #(#items)*
+        }
+    })
+}
+
+fn spy_item(item: &syn::TraitItem, actual_trait: &TokenStream) -> syn::Result<TokenStream> {
+    match item {
+        syn::TraitItem::Fn(item_fn) => spy_fn(item_fn, actual_trait),
+        _ => forward_assoc_item(item, actual_trait)
+            .ok_or_else(|| syn::Error::new(item.span(), "unsupported trait item")),
+    }
+}
+
+fn spy_fn(item_fn: &syn::TraitItemFn, actual_trait: &TokenStream) -> syn::Result<TokenStream> {
+    reject_self(&item_fn.sig, "spy")?;
+    let mut sig = item_fn.sig.clone();
+    let dot_await = sig.asyncness.map(|_| quote! { .await });
+
+    let args = rename_args(&mut sig);
+    let ident = &sig.ident;
+    let method = ident.to_string();
+    let output_ty = match &sig.output {
+        syn::ReturnType::Type(_, ty) if !contains_impl_trait(ty) => Some(quote! { : #ty }),
+        _ => None,
+    };
+
+    let split = match sig.receiver() {
+        Some(receiver) if receiver.colon_token.is_none() && receiver.reference.is_some() => {
+            if receiver.mutability.is_some() {
+                quote! { ::implementation::Spy::__split_mut(self) }
+            } else {
+                quote! { ::implementation::Spy::__split(self) }
+            }
+        }
+        Some(receiver) if receiver.colon_token.is_none() => {
+            return Ok(quote! {
+                #sig {
+                    #actual_trait::#ident(::implementation::Impl::new(self.into_inner()), #(#args),*) #dot_await
+                }
+            })
+        }
+        Some(receiver) => {
+            return Err(syn::Error::new(
+                receiver.span(),
+                "unsupported receiver type",
+            ));
+        }
+        None => {
+            return Ok(quote! {
+                #sig {
+                    #actual_trait::#ident(#(#args),*) #dot_await
+                }
+            })
+        }
+    };
+
+    Ok(quote! {
+        #sig {
+            let (actual, calls) = #split;
+            let call = ::implementation::Call::new(#method, &[#(&#args),*]);
+            let output #output_ty = #actual_trait::#ident(actual, #(#args),*) #dot_await;
+            calls.borrow_mut().push(call.returning(&output));
+            output
+        }
+    })
+}
@@ -0,0 +1,175 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "#[trait_] takes no arguments",
+        ));
+    }
+    match syn::parse2(input)? {
+        syn::Item::Trait(item_trait) => expand_trait(item_trait),
+        syn::Item::Impl(item_impl) => expand_impl(item_impl),
+        item => Err(syn::Error::new(
+            item.span(),
+            "#[trait_] must be applied to a trait, or to its actual implementation",
+        )),
+    }
+}
+
+/// Make the trait a subtrait of `Checked<Marker>`, which `Impl<T>` only implements where the marked actual
+/// implementation implements `ActualImplFor<Impl<T>>` for the marker.
+fn expand_trait(mut item_trait: syn::ItemTrait) -> syn::Result<TokenStream> {
+    item_trait.attrs.extend([
+        syn::parse_quote!(#[doc = ""]),
+        syn::parse_quote!(#[doc = "This trait has one actual implementation, written generically for [`Impl<T>`](::implementation::Impl)."]),
+        syn::parse_quote!(#[doc = "Other implementations are fakes."]),
+    ]);
+
+    let vis = &item_trait.vis;
+    let marker_ident = marker_ident(&item_trait.ident);
+    let marker_generics = marker_generics(&item_trait.generics);
+    let (impl_generics, ty_generics, where_clause) = item_trait.generics.split_for_impl();
+
+    let lifetimes = item_trait.generics.lifetimes().map(|param| &param.lifetime);
+    let types = item_trait.generics.type_params().map(|param| &param.ident);
+    let marker = quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        #vis struct #marker_ident #marker_generics(
+            ::core::marker::PhantomData<fn() -> (#(&#lifetimes (),)* #(*const #types,)*)>,
+        );
+    };
+
+    // unimock implements the trait for `Unimock`, which is a fake.
+    let unimock = item_trait
+        .attrs
+        .iter()
+        .any(|attr| {
+            attr.path()
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "unimock")
+        })
+        .then(|| {
+            quote! {
+                impl #impl_generics ::implementation::__private::Checked<#marker_ident #ty_generics> for ::unimock::Unimock #where_clause {}
+            }
+        });
+
+    let checked: syn::TypeParamBound = syn::parse_quote! {
+        ::implementation::__private::Checked<#marker_ident #ty_generics>
+    };
+    item_trait.colon_token.get_or_insert_with(Default::default);
+    item_trait.supertraits.push(checked);
+
+    Ok(quote! {
+        #item_trait
+
+        #marker
+
+        #unimock
+    })
+}
+
+/// Implement `ActualImplFor` for the marker of the trait, with the generics and bounds of the actual implementation.
+fn expand_impl(item_impl: syn::ItemImpl) -> syn::Result<TokenStream> {
+    let trait_path = match &item_impl.trait_ {
+        Some((None, path, _)) => path,
+        _ => {
+            return Err(syn::Error::new(
+                item_impl.impl_token.span,
+                "#[trait_] must be applied to a trait, or to its actual implementation",
+            ))
+        }
+    };
+    // `#[actual]` adds bounds to the implementation, which the marker needs to see.
+    if let Some(attr) = item_impl.attrs.iter().find(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "actual")
+    }) {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "#[actual] must be placed above #[trait_]",
+        ));
+    }
+    check_self_ty(&item_impl)?;
+
+    let mut marker_path = trait_path.clone();
+    let last = marker_path.segments.last_mut().unwrap();
+    last.ident = marker_ident(&last.ident);
+    let self_ty = &item_impl.self_ty;
+    let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+
+    Ok(quote! {
+        #item_impl
+
+        impl #impl_generics ::implementation::__private::ActualImplFor<#self_ty> for #marker_path #where_clause {}
+    })
+}
+
+/// Check that the implementation is for `Impl<T>`, where `T` is a type parameter of the implementation.
+fn check_self_ty(item_impl: &syn::ItemImpl) -> syn::Result<()> {
+    let error = || {
+        syn::Error::new_spanned(
+            &item_impl.self_ty,
+            "the actual implementation must be written for `Impl<T>`, generic over `T`",
+        )
+    };
+    let syn::Type::Path(type_path) = item_impl.self_ty.as_ref() else {
+        return Err(error());
+    };
+    let segment = type_path.path.segments.last().ok_or_else(error)?;
+    if type_path.qself.is_some() || segment.ident != "Impl" {
+        return Err(error());
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return Err(error());
+    };
+    match args.args.first() {
+        Some(syn::GenericArgument::Type(syn::Type::Path(arg)))
+            if args.args.len() == 1
+                && arg.qself.is_none()
+                && item_impl
+                    .generics
+                    .type_params()
+                    .any(|param| arg.path.is_ident(&param.ident)) =>
+        {
+            Ok(())
+        }
+        _ => Err(error()),
+    }
+}
+
+fn marker_ident(trait_ident: &syn::Ident) -> syn::Ident {
+    format_ident!("__{}ActualImpl", trait_ident)
+}
+
+/// The parameters of the trait, without bounds or defaults, and with type parameters that may be unsized.
+fn marker_generics(generics: &syn::Generics) -> syn::Generics {
+    let mut generics = generics.clone();
+    generics.where_clause = None;
+    for param in &mut generics.params {
+        match param {
+            syn::GenericParam::Lifetime(param) => {
+                param.colon_token = None;
+                param.bounds.clear();
+            }
+            syn::GenericParam::Type(param) => {
+                param.colon_token = Some(Default::default());
+                param.bounds = syn::parse_quote!(?Sized);
+                param.eq_token = None;
+                param.default = None;
+            }
+            syn::GenericParam::Const(param) => {
+                param.eq_token = None;
+                param.default = None;
+            }
+        }
+    }
+    generics
+}
@@ -0,0 +1,103 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+
+pub fn expand(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "#[unmock] takes no arguments",
+        ));
+    }
+    let mut item_trait: syn::ItemTrait = syn::parse2(input)?;
+
+    let trait_ident = &item_trait.ident;
+    let unmocks: Vec<TokenStream> = item_trait
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::TraitItem::Fn(item_fn) => Some(unmock_fn(trait_ident, &item_fn.sig)),
+            _ => None,
+        })
+        .collect();
+
+    let unimock_attr = item_trait
+        .attrs
+        .iter_mut()
+        .find(|attr| {
+            attr.path()
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "unimock")
+        })
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                trait_ident,
+                "#[unmock] must be placed above a #[unimock] attribute",
+            )
+        })?;
+
+    let mut args = match &unimock_attr.meta {
+        syn::Meta::Path(_) => TokenStream::new(),
+        syn::Meta::List(list) => list.tokens.clone(),
+        syn::Meta::NameValue(name_value) => {
+            return Err(syn::Error::new_spanned(
+                name_value,
+                "unsupported #[unimock] attribute",
+            ))
+        }
+    };
+    if let Some(unmock_with) = args
+        .clone()
+        .into_iter()
+        .find(|token| matches!(token, TokenTree::Ident(ident) if ident == "unmock_with"))
+    {
+        return Err(syn::Error::new_spanned(
+            unmock_with,
+            "unmock_with is generated by #[unmock]",
+        ));
+    }
+    if !args.is_empty()
+        && !matches!(args.clone().into_iter().last(), Some(TokenTree::Punct(punct)) if punct.as_char() == ',')
+    {
+        args.extend(quote! { , });
+    }
+    args.extend(quote! { unmock_with = [#(#unmocks),*] });
+
+    let path = unimock_attr.path().clone();
+    *unimock_attr = syn::parse_quote!(#[#path(#args)]);
+
+    Ok(quote! { #item_trait })
+}
+
+/// The unmock expression of one method, calling the actual implementation for `Impl<Unimock>`.
+fn unmock_fn(trait_ident: &syn::Ident, sig: &syn::Signature) -> TokenStream {
+    let receiver = match sig.receiver() {
+        Some(receiver) if receiver.colon_token.is_some() => return quote! { _ },
+        // unimock does not support unmocking `&mut self` methods
+        Some(receiver) if receiver.mutability.is_some() => return quote! { _ },
+        Some(receiver) if receiver.reference.is_some() => {
+            quote! { ::implementation::Impl::from_ref(self) }
+        }
+        Some(_) => quote! { ::implementation::Impl::new(self) },
+        None => return quote! { _ },
+    };
+
+    let mut args = vec![];
+    for input in &sig.inputs {
+        match input {
+            syn::FnArg::Receiver(_) => {}
+            syn::FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+                syn::Pat::Ident(pat_ident) => {
+                    let ident = &pat_ident.ident;
+                    args.push(quote! { #ident });
+                }
+                _ => return quote! { _ },
+            },
+        }
+    }
+
+    let ident = &sig.ident;
+    quote! {
+        #trait_ident::#ident(#receiver #(, #args)*)
+    }
+}
@@ -0,0 +1,106 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+
+/// Name the typed arguments `arg0`, `arg1`, etc. and return those names.
+pub fn rename_args(sig: &mut syn::Signature) -> Vec<syn::Ident> {
+    sig.inputs
+        .iter_mut()
+        .filter_map(|input| match input {
+            syn::FnArg::Typed(pat_type) => Some(pat_type),
+            syn::FnArg::Receiver(_) => None,
+        })
+        .enumerate()
+        .map(|(index, pat_type)| {
+            let ident = format_ident!("arg{}", index);
+            *pat_type.pat = syn::parse_quote!(#ident);
+            ident
+        })
+        .collect()
+}
+
+pub fn contains_impl_trait(ty: &syn::Type) -> bool {
+    fn contains_impl(stream: TokenStream) -> bool {
+        stream.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => ident == "impl",
+            TokenTree::Group(group) => contains_impl(group.stream()),
+            _ => false,
+        })
+    }
+
+    contains_impl(ty.to_token_stream())
+}
+
+/// Whether the tokens mention `Self` itself, as opposed to an associated item like `Self::Item`.
+pub fn mentions_self(stream: TokenStream) -> bool {
+    let mut tokens = stream.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "Self" => match tokens.peek() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {}
+                _ => return true,
+            },
+            TokenTree::Group(group) if mentions_self(group.stream()) => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Reject `Self` in the argument or return types of a method forwarded from a wrapper type,
+/// as the actual implementation's `Self` is not the wrapper.
+pub fn reject_self(sig: &syn::Signature, attr: &str) -> syn::Result<()> {
+    let arg_types = sig.inputs.iter().filter_map(|input| match input {
+        syn::FnArg::Typed(pat_type) => Some(pat_type.ty.as_ref()),
+        syn::FnArg::Receiver(_) => None,
+    });
+    let output_type = match &sig.output {
+        syn::ReturnType::Type(_, ty) => Some(ty.as_ref()),
+        syn::ReturnType::Default => None,
+    };
+    match arg_types
+        .chain(output_type)
+        .find(|ty| mentions_self(ty.to_token_stream()))
+    {
+        Some(ty) => Err(syn::Error::new_spanned(
+            ty,
+            format!("#[{attr}] does not support `Self` in argument or return types"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// A type parameter name that is not already used by the trait.
+pub fn fresh_type_param(generics: &syn::Generics) -> syn::Ident {
+    (0usize..)
+        .map(|n| match n {
+            0 => format_ident!("T"),
+            n => format_ident!("T{}", n),
+        })
+        .find(|ident| !generics.type_params().any(|param| &param.ident == ident))
+        .unwrap()
+}
+
+/// Forward an associated type or const of a trait to the implementation of `actual_trait`.
+pub fn forward_assoc_item(
+    item: &syn::TraitItem,
+    actual_trait: &TokenStream,
+) -> Option<TokenStream> {
+    match item {
+        syn::TraitItem::Type(item_type) => {
+            let ident = &item_type.ident;
+            let (_, ty_generics, where_clause) = item_type.generics.split_for_impl();
+            let generics = &item_type.generics;
+            Some(quote! {
+                type #ident #generics = #actual_trait::#ident #ty_generics #where_clause;
+            })
+        }
+        syn::TraitItem::Const(item_const) => {
+            let ident = &item_const.ident;
+            let ty = &item_const.ty;
+            Some(quote! {
+                const #ident: #ty = #actual_trait::#ident;
+            })
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,77 @@
+/// Runtime selection between an actual and a fake implementation.
+///
+/// Actual and fake implementations live on different types, so choosing between them at runtime
+/// would normally require a `Box<dyn Trait>`. A `Dispatch<Impl<T>, F>` holds either one, and with the
+/// `macros` feature, the `#[dispatch]` trait attribute implements the trait for it by matching on the variant:
+///
+/// ```rust
+/// # #[cfg(feature = "macros")]
+/// # {
+/// use implementation::{Fake, Impl, Dispatch};
+///
+/// #[implementation::dispatch]
+/// trait SendEmail {
+///     fn send_email(&self, to: &str) -> String;
+/// }
+///
+/// impl<T> SendEmail for Impl<T> {
+///     fn send_email(&self, to: &str) -> String {
+///         format!("sent to {to}")
+///     }
+/// }
+///
+/// impl SendEmail for Fake<()> {
+///     fn send_email(&self, to: &str) -> String {
+///         format!("dry run: would have sent to {to}")
+///     }
+/// }
+///
+/// fn app(mailer: &impl SendEmail) -> String {
+///     mailer.send_email("user@example.com")
+/// }
+///
+/// let dry_run = true;
+/// let mailer = if dry_run {
+///     Dispatch::Fake(Fake::new(()))
+/// } else {
+///     Dispatch::Actual(Impl::new(()))
+/// };
+///
+/// assert_eq!(app(&mailer), "dry run: would have sent to user@example.com");
+/// # }
+/// ```
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Dispatch<A, F> {
+    /// The actual implementation.
+    Actual(A),
+    /// The fake implementation.
+    Fake(F),
+}
+
+impl<A, F> Dispatch<A, F> {
+    /// Whether the actual implementation is selected.
+    pub fn is_actual(&self) -> bool {
+        matches!(self, Dispatch::Actual(_))
+    }
+
+    /// Whether the fake implementation is selected.
+    pub fn is_fake(&self) -> bool {
+        matches!(self, Dispatch::Fake(_))
+    }
+
+    /// Convert from `&Dispatch<A, F>` to `Dispatch<&A, &F>`.
+    pub fn as_ref(&self) -> Dispatch<&A, &F> {
+        match self {
+            Dispatch::Actual(actual) => Dispatch::Actual(actual),
+            Dispatch::Fake(fake) => Dispatch::Fake(fake),
+        }
+    }
+
+    /// Convert from `&mut Dispatch<A, F>` to `Dispatch<&mut A, &mut F>`.
+    pub fn as_mut(&mut self) -> Dispatch<&mut A, &mut F> {
+        match self {
+            Dispatch::Actual(actual) => Dispatch::Actual(actual),
+            Dispatch::Fake(fake) => Dispatch::Fake(fake),
+        }
+    }
+}
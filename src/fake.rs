@@ -0,0 +1,167 @@
+use crate::Impl;
+
+/// Wrapper type for targeting fake implementation.
+///
+/// [Fake] is the counterpart of [Impl](crate::Impl). Where `Impl<T>` is reserved for the one
+/// actual implementation of a trait, `Fake<T>` is a standard target for the fake ones. Each `T`
+/// represents a scenario that a fake implementation is written for:
+///
+/// ```rust
+/// use implementation::{Fake, Impl};
+///
+/// # struct Website;
+/// trait ScrapeTheInternet {
+///     fn scrape_the_internet(&self) -> Vec<Website>;
+/// }
+///
+/// impl<T> ScrapeTheInternet for Impl<T> {
+///     fn scrape_the_internet(&self) -> Vec<Website> {
+///         todo!("find all the web pages, etc")
+///     }
+/// }
+///
+/// struct NoInternet;
+///
+/// impl ScrapeTheInternet for Fake<NoInternet> {
+///     fn scrape_the_internet(&self) -> Vec<Website> {
+///         vec![]
+///     }
+/// }
+///
+/// assert!(Fake::new(NoInternet).scrape_the_internet().is_empty());
+/// ```
+///
+/// The generic actual implementation for `Impl<T>` and the specialized fake implementations
+/// never overlap, as they target distinct types.
+///
+/// A fake may call through to the actual implementation using [Actual] or [call_actual](crate::call_actual).
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Fake<T>(T);
+
+/// Access to the actual implementation from a fake one.
+///
+/// ```rust
+/// use implementation::{Actual, Fake, Impl};
+///
+/// trait Greet {
+///     fn greet(&self) -> String;
+/// }
+///
+/// impl<T> Greet for Impl<T> {
+///     fn greet(&self) -> String {
+///         "Hello".to_string()
+///     }
+/// }
+///
+/// struct Excited;
+///
+/// impl Greet for Fake<Excited> {
+///     fn greet(&self) -> String {
+///         format!("{}!", self.actual().greet())
+///     }
+/// }
+///
+/// assert_eq!(Fake::new(Excited).greet(), "Hello!");
+/// ```
+pub trait Actual {
+    /// The `T` of the actual `Impl<T>`.
+    type Inner;
+
+    /// Borrow as the actual implementation.
+    fn actual(&self) -> &Impl<Self::Inner>;
+
+    /// Mutably borrow as the actual implementation.
+    fn actual_mut(&mut self) -> &mut Impl<Self::Inner>;
+
+    /// Convert into the actual implementation.
+    fn into_actual(self) -> Impl<Self::Inner>
+    where
+        Self: Sized;
+}
+
+impl<T> Actual for Fake<T> {
+    type Inner = T;
+
+    fn actual(&self) -> &Impl<T> {
+        Impl::from_ref(&self.0)
+    }
+
+    fn actual_mut(&mut self) -> &mut Impl<T> {
+        Impl::from_mut(&mut self.0)
+    }
+
+    fn into_actual(self) -> Impl<T> {
+        Impl::new(self.0)
+    }
+}
+
+/// Call a method of the actual implementation from a fake one.
+///
+/// `call_actual!(self.method(args))` is shorthand for `Actual::actual(self).method(args)`.
+///
+/// ```rust
+/// use implementation::{call_actual, Fake, Impl};
+///
+/// trait Double {
+///     fn double(&self, n: i32) -> i32;
+/// }
+///
+/// impl<T> Double for Impl<T> {
+///     fn double(&self, n: i32) -> i32 {
+///         n * 2
+///     }
+/// }
+///
+/// struct OffByOne;
+///
+/// impl Double for Fake<OffByOne> {
+///     fn double(&self, n: i32) -> i32 {
+///         call_actual!(self.double(n)) + 1
+///     }
+/// }
+///
+/// assert_eq!(Fake::new(OffByOne).double(2), 5);
+/// ```
+#[macro_export]
+macro_rules! call_actual {
+    ($this:ident . $($call:tt)+) => {
+        $crate::Actual::actual($this).$($call)+
+    };
+}
+
+impl<T> Fake<T> {
+    /// Construct a new [Fake].
+    pub fn new(value: T) -> Fake<T> {
+        Fake(value)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Fake<T> {
+    fn from(value: T) -> Fake<T> {
+        Fake(value)
+    }
+}
+
+impl<T> core::ops::Deref for Fake<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> core::ops::DerefMut for Fake<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> AsRef<T> for Fake<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
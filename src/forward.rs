@@ -0,0 +1,286 @@
+//! Forwarding of traits implemented by the inner `T`.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::Impl;
+
+impl<T: ?Sized> Impl<T> {
+    /// Get a pinned reference to the inner `T`.
+    pub fn as_pin_ref(self: Pin<&Self>) -> Pin<&T> {
+        // SAFETY: `T` is structurally pinned. `Impl` has no `Drop` impl, is only `Unpin` when `T` is,
+        // and never moves `T` out of a pinned `Impl`.
+        unsafe { self.map_unchecked(|this| &this.0) }
+    }
+
+    /// Get a pinned mutable reference to the inner `T`.
+    pub fn as_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        // SAFETY: see `as_pin_ref`.
+        unsafe { self.map_unchecked_mut(|this| &mut this.0) }
+    }
+}
+
+impl<T: Future + ?Sized> Future for Impl<T> {
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.as_pin_mut().poll(cx)
+    }
+}
+
+#[cfg(feature = "futures-core")]
+impl<T: futures_core::Stream + ?Sized> futures_core::Stream for Impl<T> {
+    type Item = T::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.as_pin_mut().poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T: Iterator + ?Sized> Iterator for Impl<T> {
+    type Item = T::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T: DoubleEndedIterator + ?Sized> DoubleEndedIterator for Impl<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<T: ExactSizeIterator + ?Sized> ExactSizeIterator for Impl<T> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<T: core::iter::FusedIterator + ?Sized> core::iter::FusedIterator for Impl<T> {}
+
+// `Impl<T>` itself is an `IntoIterator` through being an `Iterator`, so owned forwarding
+// of `IntoIterator` would overlap with the blanket implementation in `core`.
+impl<'a, T: ?Sized> IntoIterator for &'a Impl<T>
+where
+    &'a T: IntoIterator,
+{
+    type Item = <&'a T as IntoIterator>::Item;
+    type IntoIter = <&'a T as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<T: core::fmt::Write + ?Sized> core::fmt::Write for Impl<T> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0.write_str(s)
+    }
+
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.0.write_char(c)
+    }
+
+    fn write_fmt(&mut self, args: core::fmt::Arguments<'_>) -> core::fmt::Result {
+        self.0.write_fmt(args)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize + ?Sized> serde::Serialize for Impl<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Impl<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Impl)
+    }
+}
+
+#[cfg(feature = "std")]
+mod io {
+    use std::io::{self, BufRead, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+    use std::string::String;
+    use std::vec::Vec;
+
+    use crate::Impl;
+
+    impl<T: Read + ?Sized> Read for Impl<T> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.0.read(buf)
+        }
+
+        fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+            self.0.read_vectored(bufs)
+        }
+
+        fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+            self.0.read_to_end(buf)
+        }
+
+        fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+            self.0.read_to_string(buf)
+        }
+
+        fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+            self.0.read_exact(buf)
+        }
+    }
+
+    impl<'a, T: ?Sized> Read for &'a Impl<T>
+    where
+        &'a T: Read,
+    {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            (&self.0).read(buf)
+        }
+    }
+
+    impl<T: BufRead + ?Sized> BufRead for Impl<T> {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            self.0.fill_buf()
+        }
+
+        fn consume(&mut self, amt: usize) {
+            self.0.consume(amt)
+        }
+
+        fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
+            self.0.read_until(byte, buf)
+        }
+
+        fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+            self.0.read_line(buf)
+        }
+    }
+
+    impl<T: Write + ?Sized> Write for Impl<T> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.write(buf)
+        }
+
+        fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+            self.0.write_vectored(bufs)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.0.flush()
+        }
+
+        fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+            self.0.write_all(buf)
+        }
+
+        fn write_fmt(&mut self, args: core::fmt::Arguments<'_>) -> io::Result<()> {
+            self.0.write_fmt(args)
+        }
+    }
+
+    impl<'a, T: ?Sized> Write for &'a Impl<T>
+    where
+        &'a T: Write,
+    {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            (&self.0).write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            (&self.0).flush()
+        }
+    }
+
+    impl<T: Seek + ?Sized> Seek for Impl<T> {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            self.0.seek(pos)
+        }
+
+        fn stream_position(&mut self) -> io::Result<u64> {
+            self.0.stream_position()
+        }
+    }
+
+    impl<'a, T: ?Sized> Seek for &'a Impl<T>
+    where
+        &'a T: Seek,
+    {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            (&self.0).seek(pos)
+        }
+    }
+}
+
+macro_rules! binary_ops {
+    ($($op:ident::$method:ident, $assign_op:ident::$assign_method:ident;)+) => {
+        $(
+            impl<T: core::ops::$op<U>, U> core::ops::$op<Impl<U>> for Impl<T> {
+                type Output = Impl<T::Output>;
+
+                fn $method(self, rhs: Impl<U>) -> Self::Output {
+                    Impl(self.0.$method(rhs.0))
+                }
+            }
+
+            impl<T: core::ops::$assign_op<U>, U> core::ops::$assign_op<Impl<U>> for Impl<T> {
+                fn $assign_method(&mut self, rhs: Impl<U>) {
+                    self.0.$assign_method(rhs.0)
+                }
+            }
+        )+
+    };
+}
+
+binary_ops! {
+    Add::add, AddAssign::add_assign;
+    Sub::sub, SubAssign::sub_assign;
+    Mul::mul, MulAssign::mul_assign;
+    Div::div, DivAssign::div_assign;
+    Rem::rem, RemAssign::rem_assign;
+    BitAnd::bitand, BitAndAssign::bitand_assign;
+    BitOr::bitor, BitOrAssign::bitor_assign;
+    BitXor::bitxor, BitXorAssign::bitxor_assign;
+    Shl::shl, ShlAssign::shl_assign;
+    Shr::shr, ShrAssign::shr_assign;
+}
+
+impl<T: core::ops::Neg> core::ops::Neg for Impl<T> {
+    type Output = Impl<T::Output>;
+
+    fn neg(self) -> Self::Output {
+        Impl(-self.0)
+    }
+}
+
+impl<T: core::ops::Not> core::ops::Not for Impl<T> {
+    type Output = Impl<T::Output>;
+
+    fn not(self) -> Self::Output {
+        Impl(!self.0)
+    }
+}
+
+impl<T: core::ops::Index<I> + ?Sized, I> core::ops::Index<I> for Impl<T> {
+    type Output = T::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T: core::ops::IndexMut<I> + ?Sized, I> core::ops::IndexMut<I> for Impl<T> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
@@ -0,0 +1,237 @@
+use core::fmt::Debug;
+use core::future::Future;
+
+use crate::Impl;
+
+/// Cross-cutting behaviour around the calls made to an actual implementation.
+///
+/// A [Layer] sees the name and arguments of every method called on a [Layered], and decides how to make the call.
+/// It may log, measure or otherwise observe it, and it may make the call more than once, for example to retry it.
+/// Calls to `async` methods go through [Layer::around_async] instead, which wraps the future that completes the call:
+///
+/// ```rust
+/// use core::cell::Cell;
+/// use core::fmt::Debug;
+/// use core::future::Future;
+/// use implementation::Layer;
+///
+/// #[derive(Default)]
+/// struct CountCalls(Cell<usize>);
+///
+/// impl Layer for CountCalls {
+///     fn around<A: Debug + Clone, R>(&self, method: &'static str, args: A, mut call: impl FnMut(A) -> R) -> R {
+///         self.0.set(self.0.get() + 1);
+///         call(args)
+///     }
+///
+///     async fn around_async<A: Debug, F: Future>(&self, method: &'static str, args: &A, future: F) -> F::Output {
+///         self.0.set(self.0.get() + 1);
+///         future.await
+///     }
+/// }
+///
+/// let layer = CountCalls::default();
+/// assert_eq!(layer.around("double", (21,), |(n,)| n * 2), 42);
+/// assert_eq!(layer.0.get(), 1);
+/// ```
+///
+/// The arguments are passed as a tuple, excluding the receiver. A layer that makes the call again passes it a clone of them:
+///
+/// ```rust
+/// use core::fmt::Debug;
+/// use core::future::Future;
+/// use implementation::Layer;
+///
+/// struct Retry(usize);
+///
+/// impl Layer for Retry {
+///     fn around<A: Debug + Clone, R>(&self, _: &'static str, args: A, mut call: impl FnMut(A) -> R) -> R {
+///         for _ in 0..self.0 {
+///             call(args.clone());
+///         }
+///         call(args)
+///     }
+///
+///     fn around_async<A: Debug, F: Future>(&self, _: &'static str, _: &A, future: F) -> impl Future<Output = F::Output> {
+///         future
+///     }
+/// }
+///
+/// let mut attempts = 0;
+/// assert_eq!(Retry(2).around("count", (), |()| { attempts += 1; attempts }), 3);
+/// ```
+///
+/// Layers compose as tuples, where the first layer is the outermost, and `()` is the layer that just makes the call.
+pub trait Layer {
+    /// Make the `call` to `method`, passing it the `args`.
+    fn around<A: Debug + Clone, R>(
+        &self,
+        method: &'static str,
+        args: A,
+        call: impl FnMut(A) -> R,
+    ) -> R;
+
+    /// Wrap the `future` returned by the `async` method `method`, called with the `args`.
+    fn around_async<A: Debug, F: Future>(
+        &self,
+        method: &'static str,
+        args: &A,
+        future: F,
+    ) -> impl Future<Output = F::Output>;
+}
+
+impl Layer for () {
+    fn around<A: Debug + Clone, R>(
+        &self,
+        _method: &'static str,
+        args: A,
+        mut call: impl FnMut(A) -> R,
+    ) -> R {
+        call(args)
+    }
+
+    fn around_async<A: Debug, F: Future>(
+        &self,
+        _method: &'static str,
+        _args: &A,
+        future: F,
+    ) -> impl Future<Output = F::Output> {
+        future
+    }
+}
+
+impl<L: Layer + ?Sized> Layer for &L {
+    fn around<A: Debug + Clone, R>(
+        &self,
+        method: &'static str,
+        args: A,
+        call: impl FnMut(A) -> R,
+    ) -> R {
+        (**self).around(method, args, call)
+    }
+
+    fn around_async<A: Debug, F: Future>(
+        &self,
+        method: &'static str,
+        args: &A,
+        future: F,
+    ) -> impl Future<Output = F::Output> {
+        (**self).around_async(method, args, future)
+    }
+}
+
+impl<L1: Layer, L2: Layer> Layer for (L1, L2) {
+    fn around<A: Debug + Clone, R>(
+        &self,
+        method: &'static str,
+        args: A,
+        mut call: impl FnMut(A) -> R,
+    ) -> R {
+        self.0
+            .around(method, args, |args| self.1.around(method, args, &mut call))
+    }
+
+    fn around_async<A: Debug, F: Future>(
+        &self,
+        method: &'static str,
+        args: &A,
+        future: F,
+    ) -> impl Future<Output = F::Output> {
+        let future = self.1.around_async(method, args, future);
+        self.0.around_async(method, args, future)
+    }
+}
+
+impl<L1: Layer, L2: Layer, L3: Layer> Layer for (L1, L2, L3) {
+    fn around<A: Debug + Clone, R>(
+        &self,
+        method: &'static str,
+        args: A,
+        call: impl FnMut(A) -> R,
+    ) -> R {
+        (&self.0, (&self.1, &self.2)).around(method, args, call)
+    }
+
+    fn around_async<A: Debug, F: Future>(
+        &self,
+        method: &'static str,
+        args: &A,
+        future: F,
+    ) -> impl Future<Output = F::Output> {
+        let future = self.2.around_async(method, args, future);
+        let future = self.1.around_async(method, args, future);
+        self.0.around_async(method, args, future)
+    }
+}
+
+impl<L1: Layer, L2: Layer, L3: Layer, L4: Layer> Layer for (L1, L2, L3, L4) {
+    fn around<A: Debug + Clone, R>(
+        &self,
+        method: &'static str,
+        args: A,
+        call: impl FnMut(A) -> R,
+    ) -> R {
+        (&self.0, (&self.1, &self.2, &self.3)).around(method, args, call)
+    }
+
+    fn around_async<A: Debug, F: Future>(
+        &self,
+        method: &'static str,
+        args: &A,
+        future: F,
+    ) -> impl Future<Output = F::Output> {
+        let future = self.3.around_async(method, args, future);
+        let future = self.2.around_async(method, args, future);
+        let future = self.1.around_async(method, args, future);
+        self.0.around_async(method, args, future)
+    }
+}
+
+/// Wrapper type for the actual implementation, with a [Layer] around its calls.
+///
+/// A trait annotated with `#[implementation::layered]` (requires the `macros` feature) gets an
+/// implementation for `Layered<L, T>`, given that `Impl<T>` implements it. Each method call is
+/// passed through [Layer::around], or [Layer::around_async] for `async` methods, on its way to the actual implementation.
+///
+/// Calls that have no `self` receiver are forwarded without passing through the layer.
+/// Methods taking `self` by value can only be called once, and panic when the layer makes the call again.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Layered<L, T> {
+    layer: L,
+    actual: Impl<T>,
+}
+
+impl<L, T> Layered<L, T> {
+    /// Construct a new [Layered], with `layer` around the actual implementation for `Impl<T>`.
+    pub fn new(layer: L, value: T) -> Layered<L, T> {
+        Layered {
+            layer,
+            actual: Impl::new(value),
+        }
+    }
+
+    /// Access the layer.
+    pub fn layer(&self) -> &L {
+        &self.layer
+    }
+
+    /// Access the actual implementation, bypassing the layer.
+    pub fn actual(&self) -> &Impl<T> {
+        &self.actual
+    }
+
+    /// Split into the layer and the actual implementation.
+    pub fn into_parts(self) -> (L, Impl<T>) {
+        (self.layer, self.actual)
+    }
+
+    #[doc(hidden)]
+    pub fn __split(&self) -> (&L, &Impl<T>) {
+        (&self.layer, &self.actual)
+    }
+
+    #[doc(hidden)]
+    pub fn __split_mut(&mut self) -> (&L, &mut Impl<T>) {
+        (&self.layer, &mut self.actual)
+    }
+}
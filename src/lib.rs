@@ -104,6 +104,31 @@
 //! }
 //! ```
 //!
+//! ## Macros
+//! With the `macros` feature enabled, the `#[actual]` attribute can write the generic impl and its bounds:
+//!
+//! ```rust
+//! # #[cfg(feature = "macros")]
+//! # mod example {
+//! # struct Website;
+//! # trait ScrapeTheInternet {
+//! #    fn scrape_the_internet(&self) -> Vec<Website>;
+//! # }
+//! # trait GetMaxNumberOfPages {
+//! #     fn get_max_number_of_pages(&self) -> Option<usize>;
+//! # }
+//! use implementation::Impl;
+//!
+//! #[implementation::actual]
+//! impl ScrapeTheInternet for Impl {
+//!     fn scrape_the_internet(&self) -> Vec<Website> {
+//!         let max_number_of_pages = GetMaxNumberOfPages::get_max_number_of_pages(self);
+//!         todo!("find all the web pages, etc")
+//!     }
+//! }
+//! # }
+//! ```
+//!
 //! # Explanation
 //!
 //! This crate is the solution to a trait coherence problem.
@@ -128,13 +153,89 @@
 //! That type is the [Impl] type.
 //!
 //! When we use this implementation, we can create as many fake implementations as we want.
+//! The [Fake] type is a ready-made target for those.
 //!
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod dispatch;
+mod fake;
+mod forward;
+mod layer;
+mod provide;
+mod select;
+#[cfg(feature = "alloc")]
+mod shared;
+#[cfg(feature = "alloc")]
+mod spy;
+
+pub use dispatch::Dispatch;
+pub use fake::{Actual, Fake};
+pub use layer::{Layer, Layered};
+pub use provide::{Get, Provide};
+pub use select::{Selector, TupleIndex};
+#[cfg(feature = "alloc")]
+pub use shared::SharedImpl;
+#[cfg(feature = "alloc")]
+pub use spy::{Call, Spy};
+
+#[cfg(feature = "tracing")]
+pub use implementation_macros::instrument;
+#[cfg(feature = "unimock")]
+pub use implementation_macros::unmock;
+#[cfg(feature = "macros")]
+pub use implementation_macros::{
+    actual, context, dispatch, dyn_, fake, layered, send, spy, trait_, Accessors, Project, Provide,
+};
+
+/// Items used by macro-generated code.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    pub use alloc::boxed::Box;
+    #[cfg(feature = "tracing")]
+    pub use tracing;
+
+    /// Call a closure once, letting it return borrows of the variables it captures by move.
+    #[cfg(feature = "tracing")]
+    pub fn call_once<R>(f: impl FnOnce() -> R) -> R {
+        f()
+    }
+
+    /// Implemented by the marker `M` of a `#[trait_]` trait, for the `Impl<T>` that its actual implementation covers.
+    #[diagnostic::on_unimplemented(
+        message = "`{T}` has no actual implementation marked with #[trait_]",
+        note = "mark the actual implementation with `#[implementation::trait_]`"
+    )]
+    pub trait ActualImplFor<T: ?Sized> {}
+
+    /// Supertrait of a `#[trait_]` trait with the marker `M`, restricting its implementations for `Impl<T>`
+    /// to the actual one, while leaving the fake wrappers free to implement it.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` is not covered by the actual implementation of a #[trait_] trait",
+        note = "mark the actual implementation with `#[implementation::trait_]`, and implement fakes for `Fake<T>`"
+    )]
+    pub trait Checked<M: ?Sized> {}
+
+    impl<T: ?Sized, M: ActualImplFor<crate::Impl<T>> + ?Sized> Checked<M> for crate::Impl<T> {}
+    impl<T, M: ?Sized> Checked<M> for crate::Fake<T> {}
+    impl<L, T, M: ?Sized> Checked<M> for crate::Layered<L, T> {}
+    impl<A, F, M: ?Sized> Checked<M> for crate::Dispatch<A, F> {}
+    #[cfg(feature = "alloc")]
+    impl<T, M: ?Sized> Checked<M> for crate::Spy<T> {}
+    #[cfg(feature = "alloc")]
+    impl<D: ?Sized, M: ?Sized> Checked<M> for Box<D> {}
+}
+
 /// Wrapper type for targeting and accessing actual implementation.
 ///
-/// [Impl] has smart-pointer capabilities, as it implements [std::ops::Deref] and [std::ops::DerefMut].
+/// [Impl] has smart-pointer capabilities, as it implements [Deref](core::ops::Deref) and [DerefMut](core::ops::DerefMut).
 /// You may freely choose what kind of `T` you want to wrap. It may be an owned one or it could be
 /// a `&T`. Each have different tradeoffs.
 ///
@@ -154,6 +255,10 @@
 /// }
 /// ```
 ///
+/// [Project] makes the traits implemented by such sub-implementations available on the outer `Impl`.
+///
+/// With the `alloc` feature, `T` may also live on the heap, for example through `Impl::new_arc` or `Impl::share`.
+///
 /// A referenced `&T` makes it possible to _borrow_ an `Impl` from any `T`, but that _could_ prove to be
 /// more troublesome in some implementations. This also will require a reference-within-reference
 /// design in trait methods with a `&self` receiver, and some more boilerplate if it needs to be cloned:
@@ -184,8 +289,131 @@
 ///     }
 /// }
 /// ```
+///
+/// [Impl::as_ref_impl] and [Impl::cloned_impl] convert between the two.
+/// Since `Impl<T>` is `repr(transparent)`, a `&T` can also be borrowed directly as a `&Impl<T>` using [Impl::from_ref].
+/// With the `alloc` feature, a `SharedImpl` can hand out `Impl<&T>` views to any number of threads instead.
+///
+/// # Unsized types
+/// `T` may be unsized, as in `Impl<str>`, `Impl<[T]>` or `Impl<dyn Trait>`. Such an `Impl` lives behind a pointer,
+/// either borrowed through [Impl::from_ref] or boxed through unsizing coercion or `Impl::from_box`.
+/// An actual implementation written for `Impl<T>` with `T: ?Sized` then also serves trait-object contexts,
+/// as in plugin-style applications:
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use implementation::Impl;
+///
+/// trait Context {
+///     fn name(&self) -> &str;
+/// }
+///
+/// trait Greet {
+///     fn greet(&self) -> String;
+/// }
+///
+/// impl<T: Context + ?Sized> Greet for Impl<T> {
+///     fn greet(&self) -> String {
+///         format!("Hello, {}!", self.name())
+///     }
+/// }
+///
+/// struct Plugin;
+///
+/// impl Context for Plugin {
+///     fn name(&self) -> &str {
+///         "plugin"
+///     }
+/// }
+///
+/// let context: Box<Impl<dyn Context>> = Box::new(Impl::new(Plugin));
+/// assert_eq!(context.greet(), "Hello, plugin!");
+///
+/// let context: Box<dyn Context> = Box::new(Plugin);
+/// assert_eq!(Impl::from_box(context).greet(), "Hello, plugin!");
+///
+/// let items: &Impl<[i32]> = Impl::from_ref(&[1, 2, 3]);
+/// assert_eq!(items.len(), 3);
+/// # }
+/// ```
+///
+/// # Trait forwarding
+/// `Impl<T>` implements [Future](core::future::Future), [Iterator], [DoubleEndedIterator] and [ExactSizeIterator] when `T` does,
+/// and `futures_core::Stream` with the `futures-core` feature. `&Impl<T>` implements [IntoIterator] when `&T` does.
+/// Wrapped computations can therefore be awaited or iterated directly:
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// let sum: i32 = Impl::new([1, 2, 3].into_iter()).rev().sum();
+/// assert_eq!(sum, 6);
+///
+/// async fn wrapped() -> i32 {
+///     Impl::new(async { 42 }).await
+/// }
+/// ```
+///
+/// Likewise, [Display](core::fmt::Display) and [core::fmt::Write] are forwarded, and with the `std` feature
+/// also `std::error::Error` and the `std::io` traits `Read`, `BufRead`, `Write` and `Seek`.
+/// An `Impl<File>` or `Impl<TcpStream>` can be passed to any API expecting those:
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
+/// use implementation::Impl;
+/// use std::io::{BufRead, Write};
+///
+/// let mut writer = Impl::new(Vec::new());
+/// writeln!(writer, "first").unwrap();
+/// writeln!(writer, "second").unwrap();
+///
+/// let reader = Impl::new(writer.as_slice());
+/// let lines: Vec<String> = reader.lines().map(Result::unwrap).collect();
+/// assert_eq!(lines, ["first", "second"]);
+/// # }
+/// ```
+///
+/// The arithmetic, bitwise and shift operators, along with their `*Assign` variants, work between two `Impl`s
+/// and produce an `Impl` of the output. [Neg](core::ops::Neg), [Not](core::ops::Not),
+/// [Index](core::ops::Index) and [IndexMut](core::ops::IndexMut) are forwarded too:
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// let (a, b) = (Impl::new(12), Impl::new(5));
+/// assert_eq!(a + b, Impl::new(17));
+/// assert_eq!(-a, Impl::new(-12));
+/// ```
+///
+/// With the `serde` feature, `Impl<T>` serializes and deserializes exactly like `T`, including nested `Impl` fields:
+///
+/// ```rust
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use implementation::Impl;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct MyConfig {
+///     param1: i32,
+///     sub_config: Impl<SubConfig>,
+/// }
+///
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct SubConfig {
+///     param2: i32,
+/// }
+///
+/// let json = r#"{"param1":1,"sub_config":{"param2":2}}"#;
+/// let config: Impl<MyConfig> = serde_json::from_str(json).unwrap();
+/// assert_eq!(config.sub_config.param2, 2);
+/// assert_eq!(serde_json::to_string(&config).unwrap(), json);
+/// # }
+/// ```
 #[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-pub struct Impl<T>(T);
+#[repr(transparent)]
+pub struct Impl<T: ?Sized>(T);
 
 impl<T> Impl<T> {
     /// Construct a new [Impl].
@@ -193,9 +421,265 @@ impl<T> Impl<T> {
         Impl(value)
     }
 
+    /// Borrow a `&[T]` as an `&[Impl<T>]`.
+    pub fn from_slice(slice: &[T]) -> &[Impl<T>] {
+        // SAFETY: `Impl<T>` is `repr(transparent)` over `T`, so the slices have the same layout.
+        unsafe { core::slice::from_raw_parts(slice.as_ptr() as *const Impl<T>, slice.len()) }
+    }
+
+    /// Borrow a `&mut [T]` as an `&mut [Impl<T>]`.
+    pub fn from_mut_slice(slice: &mut [T]) -> &mut [Impl<T>] {
+        // SAFETY: `Impl<T>` is `repr(transparent)` over `T`, so the slices have the same layout.
+        unsafe { core::slice::from_raw_parts_mut(slice.as_mut_ptr() as *mut Impl<T>, slice.len()) }
+    }
+
     pub fn into_inner(self) -> T {
         self.0
     }
+
+    /// Map the `T` to a `U`, staying inside the `Impl`.
+    ///
+    /// ```rust
+    /// use implementation::Impl;
+    ///
+    /// let port = Impl::new("8080").map_impl(|port| port.parse::<u16>().unwrap());
+    /// assert_eq!(port, Impl::new(8080));
+    /// ```
+    ///
+    /// The combinators of `Impl` are suffixed with `_impl`, so that they don't shadow the methods of `T`
+    /// reached through [Deref](core::ops::Deref), or the methods of traits forwarded to `T`:
+    ///
+    /// ```rust
+    /// use implementation::Impl;
+    ///
+    /// let port = Impl::new(Some("8080"));
+    /// assert_eq!(port.map(|port| port.len()), Some(4));
+    /// assert_eq!(port.map_impl(|port| port.is_some()), Impl::new(true));
+    /// ```
+    pub fn map_impl<U, F: FnOnce(T) -> U>(self, f: F) -> Impl<U> {
+        Impl(f(self.0))
+    }
+
+    /// Map the `T` to an `Impl<U>`.
+    ///
+    /// ```rust
+    /// use implementation::Impl;
+    ///
+    /// let port = Impl::new("8080").and_then_impl(|port| Impl::new(port.len()));
+    /// assert_eq!(port, Impl::new(4));
+    /// ```
+    pub fn and_then_impl<U, F: FnOnce(T) -> Impl<U>>(self, f: F) -> Impl<U> {
+        f(self.0)
+    }
+
+    /// Combine with another `Impl` into an `Impl` of a tuple.
+    ///
+    /// ```rust
+    /// use implementation::Impl;
+    ///
+    /// let context = Impl::new("db").zip_impl(Impl::new("http"));
+    /// assert_eq!(context, Impl::new(("db", "http")));
+    /// ```
+    pub fn zip_impl<U>(self, other: Impl<U>) -> Impl<(T, U)> {
+        Impl((self.0, other.0))
+    }
+
+    /// Call `f` with a reference to the `T`, and return the `Impl` unchanged.
+    ///
+    /// ```rust
+    /// use implementation::Impl;
+    ///
+    /// let mut seen = None;
+    /// let port = Impl::new(8080).inspect_impl(|port| seen = Some(*port));
+    /// assert_eq!((port, seen), (Impl::new(8080), Some(8080)));
+    /// ```
+    pub fn inspect_impl<F: FnOnce(&T)>(self, f: F) -> Impl<T> {
+        f(&self.0);
+        self
+    }
+}
+
+impl<T: ?Sized> Impl<T> {
+    /// Borrow a `&T` as an `&Impl<T>`.
+    ///
+    /// This makes actual implementations written for an owned `Impl<T>` available from just a reference,
+    /// without cloning or resorting to `Impl<&T>`:
+    ///
+    /// ```rust
+    /// use implementation::Impl;
+    ///
+    /// trait Len {
+    ///     fn len(&self) -> usize;
+    /// }
+    ///
+    /// impl Len for Impl<String> {
+    ///     fn len(&self) -> usize {
+    ///         self.as_str().len()
+    ///     }
+    /// }
+    ///
+    /// let text = String::from("text");
+    /// assert_eq!(Impl::from_ref(&text).len(), 4);
+    /// ```
+    pub fn from_ref(value: &T) -> &Impl<T> {
+        // SAFETY: `Impl<T>` is `repr(transparent)` over `T`.
+        unsafe { &*(value as *const T as *const Impl<T>) }
+    }
+
+    /// Borrow a `&mut T` as an `&mut Impl<T>`.
+    pub fn from_mut(value: &mut T) -> &mut Impl<T> {
+        // SAFETY: `Impl<T>` is `repr(transparent)` over `T`.
+        unsafe { &mut *(value as *mut T as *mut Impl<T>) }
+    }
+
+    /// Convert from `&Impl<T>` to `Impl<&T>`.
+    ///
+    /// This makes actual implementations written for `Impl<&T>` available from an owned `Impl<T>`:
+    ///
+    /// ```rust
+    /// use implementation::Impl;
+    ///
+    /// trait Len {
+    ///     fn len(&self) -> usize;
+    /// }
+    ///
+    /// impl<'t> Len for Impl<&'t String> {
+    ///     fn len(&self) -> usize {
+    ///         self.as_str().len()
+    ///     }
+    /// }
+    ///
+    /// let owned = Impl::new(String::from("text"));
+    /// assert_eq!(owned.as_ref_impl().len(), 4);
+    /// ```
+    pub fn as_ref_impl(&self) -> Impl<&T> {
+        Impl(&self.0)
+    }
+
+    /// Convert from `&mut Impl<T>` to `Impl<&mut T>`.
+    pub fn as_mut_impl(&mut self) -> Impl<&mut T> {
+        Impl(&mut self.0)
+    }
+}
+
+impl<T: core::ops::Deref + ?Sized> Impl<T> {
+    /// Convert from `&Impl<T>` to `Impl<&T::Target>`.
+    ///
+    /// ```rust
+    /// use implementation::Impl;
+    ///
+    /// let owned = Impl::new(String::from("text"));
+    /// let borrowed: Impl<&str> = owned.as_deref_impl();
+    /// assert_eq!(borrowed, Impl::new("text"));
+    /// ```
+    pub fn as_deref_impl(&self) -> Impl<&T::Target> {
+        Impl(self.0.deref())
+    }
+}
+
+impl<T: Clone> Impl<&T> {
+    /// Clone the referenced `T` into an owned `Impl<T>`.
+    pub fn cloned_impl(self) -> Impl<T> {
+        Impl(self.0.clone())
+    }
+}
+
+impl<T: Copy> Impl<&T> {
+    /// Copy the referenced `T` into an owned `Impl<T>`.
+    pub fn copied_impl(self) -> Impl<T> {
+        Impl(*self.0)
+    }
+}
+
+impl<T: Clone> Impl<&mut T> {
+    /// Clone the referenced `T` into an owned `Impl<T>`.
+    pub fn cloned_impl(self) -> Impl<T> {
+        Impl(self.0.clone())
+    }
+}
+
+impl<T: Copy> Impl<&mut T> {
+    /// Copy the referenced `T` into an owned `Impl<T>`.
+    pub fn copied_impl(self) -> Impl<T> {
+        Impl(*self.0)
+    }
+}
+
+impl<T> Impl<Option<T>> {
+    /// Transpose an `Impl` of an [Option] into an [Option] of an `Impl`.
+    pub fn transpose_impl(self) -> Option<Impl<T>> {
+        self.0.map(Impl)
+    }
+}
+
+impl<T, E> Impl<Result<T, E>> {
+    /// Transpose an `Impl` of a [Result] into a [Result] of an `Impl`.
+    pub fn transpose_impl(self) -> Result<Impl<T>, E> {
+        self.0.map(Impl)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Impl<T> {
+    /// Construct a new [Impl] owning a boxed `T`.
+    pub fn new_box(value: T) -> Impl<alloc::boxed::Box<T>> {
+        Impl(alloc::boxed::Box::new(value))
+    }
+
+    /// Construct a new [Impl] owning a reference-counted `T`.
+    pub fn new_rc(value: T) -> Impl<alloc::rc::Rc<T>> {
+        Impl(alloc::rc::Rc::new(value))
+    }
+
+    /// Construct a new [Impl] owning an atomically reference-counted `T`.
+    pub fn new_arc(value: T) -> Impl<alloc::sync::Arc<T>> {
+        Impl(alloc::sync::Arc::new(value))
+    }
+
+    /// Move the `T` into an [Arc](alloc::sync::Arc), making the [Impl] cheap to clone and share between threads.
+    ///
+    /// Use this when actual implementations are written for `Impl<Arc<T>>`. When they are written for `Impl<&T>`,
+    /// [SharedImpl] is a handle that produces those instead.
+    pub fn share(self) -> Impl<alloc::sync::Arc<T>> {
+        Impl(alloc::sync::Arc::new(self.0))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> Impl<T> {
+    /// Convert a `Box<T>` into a `Box<Impl<T>>`, without reallocating.
+    ///
+    /// This is also available as a [From] conversion.
+    pub fn from_box(value: alloc::boxed::Box<T>) -> alloc::boxed::Box<Impl<T>> {
+        // SAFETY: `Impl<T>` is `repr(transparent)` over `T`.
+        unsafe { alloc::boxed::Box::from_raw(alloc::boxed::Box::into_raw(value) as *mut Impl<T>) }
+    }
+
+    /// Convert an `Rc<T>` into an `Rc<Impl<T>>`, without reallocating.
+    pub fn from_rc(value: alloc::rc::Rc<T>) -> alloc::rc::Rc<Impl<T>> {
+        // SAFETY: `Impl<T>` is `repr(transparent)` over `T`.
+        unsafe { alloc::rc::Rc::from_raw(alloc::rc::Rc::into_raw(value) as *const Impl<T>) }
+    }
+
+    /// Convert an `Arc<T>` into an `Arc<Impl<T>>`, without reallocating.
+    pub fn from_arc(value: alloc::sync::Arc<T>) -> alloc::sync::Arc<Impl<T>> {
+        // SAFETY: `Impl<T>` is `repr(transparent)` over `T`.
+        unsafe { alloc::sync::Arc::from_raw(alloc::sync::Arc::into_raw(value) as *const Impl<T>) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized + alloc::borrow::ToOwned> Impl<&T> {
+    /// Convert an `Impl<&T>` into an `Impl` of the owned counterpart of `T`.
+    ///
+    /// ```rust
+    /// use implementation::Impl;
+    ///
+    /// let owned: Impl<String> = Impl::new("text").into_owned();
+    /// ```
+    pub fn into_owned(self) -> Impl<T::Owned> {
+        Impl(self.0.to_owned())
+    }
 }
 
 impl<T> From<T> for Impl<T> {
@@ -204,7 +688,14 @@ impl<T> From<T> for Impl<T> {
     }
 }
 
-impl<T> core::ops::Deref for Impl<T> {
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> From<alloc::boxed::Box<T>> for alloc::boxed::Box<Impl<T>> {
+    fn from(value: alloc::boxed::Box<T>) -> alloc::boxed::Box<Impl<T>> {
+        Impl::from_box(value)
+    }
+}
+
+impl<T: ?Sized> core::ops::Deref for Impl<T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -212,14 +703,162 @@ impl<T> core::ops::Deref for Impl<T> {
     }
 }
 
-impl<T> core::ops::DerefMut for Impl<T> {
+impl<T: ?Sized> core::ops::DerefMut for Impl<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
 }
 
-impl<T> AsRef<T> for Impl<T> {
+impl<T: ?Sized> AsRef<T> for Impl<T> {
     fn as_ref(&self) -> &T {
         &self.0
     }
 }
+
+impl<T: ?Sized> core::borrow::Borrow<T> for Impl<T> {
+    fn borrow(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: ?Sized> core::borrow::BorrowMut<T> for Impl<T> {
+    fn borrow_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: core::fmt::Display + ?Sized> core::fmt::Display for Impl<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: std::error::Error + ?Sized> std::error::Error for Impl<T> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// Projection from an `Impl` into a sub-implementation.
+///
+/// `Project` makes it possible for an `Impl<T>` to satisfy the traits implemented by
+/// a nested `Impl<SubConfig>`. Every `Impl<T>` projects into itself, and a config
+/// owning a sub-implementation may project into that:
+///
+/// ```rust
+/// use implementation::{Impl, Project};
+///
+/// struct MyConfig {
+///     param1: i32,
+///     sub_config: Impl<SubConfig>,
+/// }
+///
+/// struct SubConfig {
+///     param2: i32,
+/// }
+///
+/// impl Project<SubConfig> for Impl<MyConfig> {
+///     fn project(&self) -> &Impl<SubConfig> {
+///         &self.sub_config
+///     }
+/// }
+/// ```
+///
+/// A trait implemented in terms of `Project<SubConfig>` is then available for both
+/// `Impl<SubConfig>` and `Impl<MyConfig>`:
+///
+/// ```rust
+/// # use implementation::{Impl, Project};
+/// # struct MyConfig { param1: i32, sub_config: Impl<SubConfig> }
+/// # struct SubConfig { param2: i32 }
+/// # impl Project<SubConfig> for Impl<MyConfig> {
+/// #     fn project(&self) -> &Impl<SubConfig> {
+/// #         &self.sub_config
+/// #     }
+/// # }
+/// trait GetParam2 {
+///     fn get_param2(&self) -> i32;
+/// }
+///
+/// impl<T> GetParam2 for Impl<T>
+///     where Impl<T>: Project<SubConfig>
+/// {
+///     fn get_param2(&self) -> i32 {
+///         Project::<SubConfig>::project(self).param2
+///     }
+/// }
+///
+/// let sub_config = Impl::new(SubConfig { param2: 2 });
+/// assert_eq!(sub_config.get_param2(), 2);
+///
+/// let my_config = Impl::new(MyConfig { param1: 1, sub_config });
+/// assert_eq!(my_config.get_param2(), 2);
+/// ```
+pub trait Project<P: ?Sized> {
+    /// Project into the sub-implementation.
+    fn project(&self) -> &Impl<P>;
+}
+
+impl<T: ?Sized> Project<T> for Impl<T> {
+    fn project(&self) -> &Impl<T> {
+        self
+    }
+}
+
+/// Marker trait implemented only by [Impl].
+///
+/// The trait is sealed, so that no other type can claim to be the actual implementation.
+/// Generic code can require `ActualImpl` to guarantee that the actual implementations of its other bounds are used,
+/// and never a fake one:
+///
+/// ```rust
+/// use implementation::{ActualImpl, Fake, Impl};
+///
+/// trait ScrapeTheInternet {
+///     fn scrape_the_internet(&self) -> usize;
+/// }
+///
+/// impl<T> ScrapeTheInternet for Impl<T> {
+///     fn scrape_the_internet(&self) -> usize {
+///         1_000_000
+///     }
+/// }
+///
+/// impl ScrapeTheInternet for Fake<()> {
+///     fn scrape_the_internet(&self) -> usize {
+///         0
+///     }
+/// }
+///
+/// fn run_in_production(app: &(impl ActualImpl + ScrapeTheInternet)) -> usize {
+///     app.scrape_the_internet()
+/// }
+///
+/// run_in_production(&Impl::new(()));
+/// ```
+///
+/// ```rust,compile_fail,E0277
+/// # use implementation::{ActualImpl, Fake};
+/// # trait ScrapeTheInternet {
+/// #     fn scrape_the_internet(&self) -> usize;
+/// # }
+/// # impl ScrapeTheInternet for Fake<()> {
+/// #     fn scrape_the_internet(&self) -> usize {
+/// #         0
+/// #     }
+/// # }
+/// # fn run_in_production(app: &(impl ActualImpl + ScrapeTheInternet)) -> usize {
+/// #     app.scrape_the_internet()
+/// # }
+/// run_in_production(&Fake::new(()));
+/// ```
+pub trait ActualImpl: sealed::Sealed {}
+
+impl<T: ?Sized> ActualImpl for Impl<T> {}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl<T: ?Sized> Sealed for crate::Impl<T> {}
+}
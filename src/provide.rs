@@ -0,0 +1,62 @@
+use crate::Impl;
+
+/// A context type that provides a dependency of type `D`.
+///
+/// `Provide` is implemented for the inner `T` of an `Impl<T>`, either by hand or using
+/// `#[derive(implementation::Provide)]` (requires the `macros` feature).
+pub trait Provide<D> {
+    /// Provide the dependency.
+    fn provide(&self) -> &D;
+}
+
+/// Typed access to a dependency of type `D`.
+///
+/// `Get<D>` is implemented for every `Impl<T>` where `T` provides `D`, which gives actual implementations
+/// one uniform way to depend on values from the context:
+///
+/// ```rust
+/// use implementation::{Get, Impl, Provide};
+///
+/// struct HttpClient;
+///
+/// impl HttpClient {
+///     fn fetch(&self, url: &str) -> String {
+///         format!("<html>{url}</html>")
+///     }
+/// }
+///
+/// trait ScrapeTheInternet {
+///     fn scrape_the_internet(&self) -> Vec<String>;
+/// }
+///
+/// impl<T> ScrapeTheInternet for Impl<T>
+///     where Impl<T>: Get<HttpClient>
+/// {
+///     fn scrape_the_internet(&self) -> Vec<String> {
+///         vec![self.get().fetch("https://example.com")]
+///     }
+/// }
+///
+/// struct App {
+///     http_client: HttpClient,
+/// }
+///
+/// impl Provide<HttpClient> for App {
+///     fn provide(&self) -> &HttpClient {
+///         &self.http_client
+///     }
+/// }
+///
+/// let app = Impl::new(App { http_client: HttpClient });
+/// assert_eq!(app.scrape_the_internet(), ["<html>https://example.com</html>"]);
+/// ```
+pub trait Get<D> {
+    /// Get the dependency.
+    fn get(&self) -> &D;
+}
+
+impl<T: Provide<D> + ?Sized, D> Get<D> for Impl<T> {
+    fn get(&self) -> &D {
+        self.0.provide()
+    }
+}
@@ -0,0 +1,79 @@
+use crate::Impl;
+
+/// Type-directed selection of an `Impl<S>` from a composite context.
+///
+/// Tuples implement `Selector` for each of their elements, so that the traits implemented for each
+/// `Impl<A>`, `Impl<B>`, etc. are usable from one `Impl<(A, B)>` through [Impl::select_impl]:
+///
+/// ```rust
+/// use implementation::Impl;
+///
+/// struct DbConfig {
+///     url: &'static str,
+/// }
+///
+/// struct HttpConfig {
+///     port: u16,
+/// }
+///
+/// trait GetDbUrl {
+///     fn get_db_url(&self) -> &str;
+/// }
+///
+/// impl GetDbUrl for Impl<DbConfig> {
+///     fn get_db_url(&self) -> &str {
+///         self.url
+///     }
+/// }
+///
+/// let context = Impl::new((DbConfig { url: "postgres://" }, HttpConfig { port: 8080 }));
+///
+/// assert_eq!(context.select_impl::<DbConfig, _>().get_db_url(), "postgres://");
+/// assert_eq!(context.select_impl::<HttpConfig, _>().port, 8080);
+/// ```
+///
+/// The `I` parameter is the [TupleIndex] of the selected element, and is normally inferred.
+/// It only needs to be spelled out when the tuple contains the same type more than once.
+///
+/// Selection is explicit, so it can't satisfy trait bounds on `Impl<(A, B)>` itself. Traits implemented in terms
+/// of [Project](crate::Project) can be made available for the tuple using `#[implementation::context]` (requires the `macros` feature).
+pub trait Selector<S, I> {
+    /// Select the `Impl<S>` at index `I`.
+    fn select(&self) -> &Impl<S>;
+}
+
+/// Type-level index of a tuple element, used by [Selector].
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct TupleIndex<const N: usize>;
+
+impl<T> Impl<T> {
+    /// Select an `Impl<S>` from the composite `T`.
+    pub fn select_impl<S, I>(&self) -> &Impl<S>
+    where
+        T: Selector<S, I>,
+    {
+        self.0.select()
+    }
+}
+
+macro_rules! tuple_selectors {
+    ($tys:tt $($index:tt => $selected:ident),+) => {
+        $(tuple_selectors!(@impl $tys $index => $selected);)+
+    };
+    (@impl ($($ty:ident),+) $index:tt => $selected:ident) => {
+        impl<$($ty),+> Selector<$selected, TupleIndex<$index>> for ($($ty,)+) {
+            fn select(&self) -> &Impl<$selected> {
+                Impl::from_ref(&self.$index)
+            }
+        }
+    };
+}
+
+tuple_selectors!((A) 0 => A);
+tuple_selectors!((A, B) 0 => A, 1 => B);
+tuple_selectors!((A, B, C) 0 => A, 1 => B, 2 => C);
+tuple_selectors!((A, B, C, D) 0 => A, 1 => B, 2 => C, 3 => D);
+tuple_selectors!((A, B, C, D, E) 0 => A, 1 => B, 2 => C, 3 => D, 4 => E);
+tuple_selectors!((A, B, C, D, E, F) 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F);
+tuple_selectors!((A, B, C, D, E, F, G) 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G);
+tuple_selectors!((A, B, C, D, E, F, G, H) 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H);
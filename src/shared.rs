@@ -0,0 +1,147 @@
+use alloc::sync::Arc;
+
+use crate::Impl;
+
+/// A cheaply cloneable, shared handle to a `T`.
+///
+/// `SharedImpl<T>` wraps an [Arc], and is `Clone + Send + Sync` when `T: Send + Sync`.
+/// It derefs to `T`, and produces borrowed `Impl<&T>` views through [SharedImpl::as_ref_impl].
+///
+/// Actual implementations written for `Impl<&T>` can then fan work out to threads, without cloning `T` itself:
+///
+/// ```rust
+/// use implementation::{Impl, SharedImpl};
+///
+/// trait DoSomething {
+///     fn something(&self) -> i32;
+/// }
+///
+/// impl<'t, T> DoSomething for Impl<&'t T> {
+///     fn something(&self) -> i32 {
+///         42
+///     }
+/// }
+///
+/// struct Config;
+///
+/// let shared = SharedImpl::new(Config);
+///
+/// let handle = std::thread::spawn({
+///     let shared = shared.clone();
+///     move || shared.as_ref_impl().something()
+/// });
+///
+/// assert_eq!(handle.join().unwrap(), 42);
+/// assert_eq!(shared.as_ref_impl().something(), 42);
+/// ```
+///
+/// [Impl::share] also moves a `T` into an [Arc], but produces an `Impl<Arc<T>>`. That is the one to use when actual
+/// implementations are written for `Impl<Arc<T>>` itself, for example to clone the [Arc] into what they spawn.
+/// `SharedImpl` is for actual implementations written for `Impl<&T>`, as it is not an [Impl] itself, only a handle producing them.
+#[derive(Debug)]
+pub struct SharedImpl<T>(Arc<T>);
+
+impl<T> SharedImpl<T> {
+    /// Construct a new [SharedImpl].
+    pub fn new(value: T) -> SharedImpl<T> {
+        SharedImpl(Arc::new(value))
+    }
+
+    /// Borrow the shared `T` as an `Impl<&T>`.
+    pub fn as_ref_impl(&self) -> Impl<&T> {
+        Impl::new(&self.0)
+    }
+
+    /// Unwrap the shared [Arc], e.g. to pass it to code that is not written for `Impl`.
+    pub fn into_arc(self) -> Arc<T> {
+        self.0
+    }
+
+    /// Produce a task from a clone of this handle, e.g. for spawning on an executor.
+    ///
+    /// The closure receives an owned [SharedImpl], so the returned future can be `'static`
+    /// and `Send` while calling async actual implementations written for `Impl<&T>`:
+    ///
+    /// ```rust
+    /// use implementation::{Impl, SharedImpl};
+    /// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    /// #     struct NoopWaker;
+    /// #     impl std::task::Wake for NoopWaker {
+    /// #         fn wake(self: std::sync::Arc<Self>) {}
+    /// #     }
+    /// #     let waker = std::task::Waker::from(std::sync::Arc::new(NoopWaker));
+    /// #     let mut cx = std::task::Context::from_waker(&waker);
+    /// #     let mut future = std::pin::pin!(future);
+    /// #     loop {
+    /// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    ///
+    /// trait Fetch {
+    ///     fn fetch(&self) -> impl std::future::Future<Output = i32> + Send;
+    /// }
+    ///
+    /// impl<'t, T: Sync> Fetch for Impl<&'t T> {
+    ///     async fn fetch(&self) -> i32 {
+    ///         42
+    ///     }
+    /// }
+    ///
+    /// fn spawn<F: std::future::Future + Send + 'static>(future: F) -> F {
+    ///     future
+    /// }
+    ///
+    /// struct Config;
+    ///
+    /// let shared = SharedImpl::new(Config);
+    /// let task = spawn(shared.task(|shared| async move { shared.as_ref_impl().fetch().await }));
+    ///
+    /// assert_eq!(block_on(task), 42);
+    /// ```
+    pub fn task<F, Fut>(&self, f: F) -> Fut
+    where
+        F: FnOnce(SharedImpl<T>) -> Fut,
+    {
+        f(self.clone())
+    }
+}
+
+impl<T> Clone for SharedImpl<T> {
+    fn clone(&self) -> Self {
+        SharedImpl(self.0.clone())
+    }
+}
+
+impl<T> From<T> for SharedImpl<T> {
+    fn from(value: T) -> SharedImpl<T> {
+        SharedImpl::new(value)
+    }
+}
+
+impl<T> From<Arc<T>> for SharedImpl<T> {
+    fn from(value: Arc<T>) -> SharedImpl<T> {
+        SharedImpl(value)
+    }
+}
+
+impl<T> From<Impl<T>> for SharedImpl<T> {
+    fn from(value: Impl<T>) -> SharedImpl<T> {
+        SharedImpl::new(value.into_inner())
+    }
+}
+
+impl<T> core::ops::Deref for SharedImpl<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for SharedImpl<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
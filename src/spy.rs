@@ -0,0 +1,104 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt::Debug;
+
+use crate::Impl;
+
+/// Wrapper type that records the calls made to the actual implementation.
+///
+/// A trait annotated with `#[implementation::spy]` (requires the `macros` feature) gets an
+/// implementation for `Spy<T>`, given that `Impl<T>` implements it. Each method call is
+/// forwarded to the actual implementation, and recorded as a [Call].
+///
+/// Calls that consume the spy, or that have no `self` receiver, are forwarded without being recorded.
+#[derive(Default, Debug)]
+pub struct Spy<T> {
+    actual: Impl<T>,
+    calls: RefCell<Vec<Call>>,
+}
+
+impl<T> Spy<T> {
+    /// Construct a new [Spy], with an empty call log.
+    pub fn new(value: T) -> Spy<T> {
+        Spy {
+            actual: Impl::new(value),
+            calls: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.actual.into_inner()
+    }
+
+    /// Access the actual implementation, bypassing the call log.
+    pub fn actual(&self) -> &Impl<T> {
+        &self.actual
+    }
+
+    /// The calls recorded so far, in call order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.calls.borrow().clone()
+    }
+
+    /// Take the calls recorded so far, leaving the call log empty.
+    pub fn take_calls(&self) -> Vec<Call> {
+        self.calls.take()
+    }
+
+    #[doc(hidden)]
+    pub fn __split(&self) -> (&Impl<T>, &RefCell<Vec<Call>>) {
+        (&self.actual, &self.calls)
+    }
+
+    #[doc(hidden)]
+    pub fn __split_mut(&mut self) -> (&mut Impl<T>, &RefCell<Vec<Call>>) {
+        (&mut self.actual, &self.calls)
+    }
+}
+
+impl<T> From<T> for Spy<T> {
+    fn from(value: T) -> Spy<T> {
+        Spy::new(value)
+    }
+}
+
+/// A method call recorded by a [Spy].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Call {
+    method: &'static str,
+    args: Vec<String>,
+    output: String,
+}
+
+impl Call {
+    /// Construct a call to `method`, with `Debug`-formatted arguments.
+    pub fn new(method: &'static str, args: &[&dyn Debug]) -> Call {
+        Call {
+            method,
+            args: args.iter().map(|arg| alloc::format!("{arg:?}")).collect(),
+            output: String::new(),
+        }
+    }
+
+    /// Set the `Debug`-formatted value returned from the call.
+    pub fn returning(mut self, output: &dyn Debug) -> Call {
+        self.output = alloc::format!("{output:?}");
+        self
+    }
+
+    /// The name of the called method.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The `Debug`-formatted arguments, excluding the receiver.
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// The `Debug`-formatted return value.
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+}
@@ -0,0 +1,212 @@
+use core::marker::PhantomPinned;
+use core::pin::{pin, Pin};
+
+use implementation::Impl;
+
+mod common;
+
+use common::{block_on, yield_now};
+
+#[test]
+fn iterator_methods_are_not_shadowed() {
+    let mut inspected = vec![];
+    let pairs: Vec<(u32, char)> = Impl::new([1, 2, 3].into_iter())
+        .map(|n| n * 10)
+        .inspect(|n| inspected.push(*n))
+        .zip("abc".chars())
+        .collect();
+    assert_eq!(pairs, [(10, 'a'), (20, 'b'), (30, 'c')]);
+    assert_eq!(inspected, [10, 20, 30]);
+}
+
+#[test]
+fn iterator_methods_through_mutable_reference_are_not_shadowed() {
+    let ports = [5432, 8080, 8443];
+    let mut iter = ports.iter();
+    iter.next();
+    assert_eq!(Impl::new(&mut iter).cloned().sum::<u32>(), 16523);
+    assert_eq!(iter.count(), 0);
+
+    let mut iter = ports.iter();
+    assert_eq!(Impl::new(&mut iter).copied().max(), Some(8443));
+    assert_eq!(iter.count(), 0);
+}
+
+#[test]
+fn poll_pinned_future() {
+    let future = pin!(Impl::new(async {
+        let borrowed = &String::from("across await");
+        yield_now().await;
+        borrowed.len()
+    }));
+    assert_eq!(block_on(future), 12);
+}
+
+/// A `!Unpin` value, which counts how many times it has been pinned.
+struct Pinned {
+    count: u32,
+    _pinned: PhantomPinned,
+}
+
+impl Pinned {
+    fn count(self: Pin<&Self>) -> u32 {
+        self.count
+    }
+
+    fn increment(self: Pin<&mut Self>) {
+        // SAFETY: `count` is not structurally pinned.
+        unsafe { self.get_unchecked_mut().count += 1 }
+    }
+}
+
+#[test]
+fn pin_projection() {
+    let mut pinned = pin!(Impl::new(Pinned {
+        count: 0,
+        _pinned: PhantomPinned,
+    }));
+    pinned.as_mut().as_pin_mut().increment();
+    pinned.as_mut().as_pin_mut().increment();
+    assert_eq!(pinned.as_ref().as_pin_ref().count(), 2);
+}
+
+#[cfg(feature = "futures-core")]
+mod stream {
+    use super::*;
+    use core::task::{Context, Poll, Waker};
+    use futures_core::Stream;
+
+    /// A `!Unpin` stream counting down to zero, yielding once between each item.
+    struct Countdown {
+        remaining: u32,
+        yielded: bool,
+        _pinned: PhantomPinned,
+    }
+
+    impl Stream for Countdown {
+        type Item = u32;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+            // SAFETY: no field is structurally pinned.
+            let this = unsafe { self.get_unchecked_mut() };
+            if this.remaining == 0 {
+                return Poll::Ready(None);
+            }
+            if !this.yielded {
+                this.yielded = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            this.yielded = false;
+            this.remaining -= 1;
+            Poll::Ready(Some(this.remaining))
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.remaining as usize, Some(self.remaining as usize))
+        }
+    }
+
+    #[test]
+    fn iterate_stream() {
+        let mut stream = pin!(Impl::new(Countdown {
+            remaining: 3,
+            yielded: false,
+            _pinned: PhantomPinned,
+        }));
+        assert_eq!(stream.size_hint(), (3, Some(3)));
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut items = vec![];
+        loop {
+            match stream.as_mut().poll_next(&mut cx) {
+                Poll::Ready(Some(item)) => items.push(item),
+                Poll::Ready(None) => break,
+                Poll::Pending => {}
+            }
+        }
+        assert_eq!(items, [2, 1, 0]);
+    }
+}
+
+#[test]
+fn fmt_write() {
+    use core::fmt::Write;
+
+    let mut out = Impl::new(String::new());
+    out.write_str("port ").unwrap();
+    write!(out, "{}", 8080).unwrap();
+    out.write_char('!').unwrap();
+    assert_eq!(out.into_inner(), "port 8080!");
+}
+
+#[cfg(feature = "std")]
+mod io {
+    use super::*;
+    use std::io::{BufRead, Cursor, Read, Seek, SeekFrom, Write};
+
+    #[test]
+    fn read() {
+        let mut input = Impl::new(&b"first line\nsecond line\n"[..]);
+        let mut word = [0; 5];
+        input.read_exact(&mut word).unwrap();
+        assert_eq!(&word, b"first");
+
+        let mut line = String::new();
+        input.read_line(&mut line).unwrap();
+        assert_eq!(line, " line\n");
+
+        let mut rest = vec![];
+        assert_eq!(input.read_to_end(&mut rest).unwrap(), 12);
+        assert_eq!(rest, b"second line\n");
+    }
+
+    #[test]
+    fn seek() {
+        let mut cursor = Impl::new(Cursor::new(b"0123456789".to_vec()));
+        assert_eq!(cursor.seek(SeekFrom::Start(3)).unwrap(), 3);
+        assert_eq!(cursor.seek(SeekFrom::Current(4)).unwrap(), 7);
+        assert_eq!(cursor.stream_position().unwrap(), 7);
+
+        let mut rest = String::new();
+        cursor.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "789");
+
+        cursor.seek(SeekFrom::End(-2)).unwrap();
+        cursor.write_all(b"XY").unwrap();
+        assert_eq!(cursor.into_inner().into_inner(), b"01234567XY");
+    }
+
+    #[test]
+    fn write() {
+        let mut out = Impl::new(vec![]);
+        out.write_all(b"port ").unwrap();
+        write!(out, "{}", 8080).unwrap();
+        out.flush().unwrap();
+        assert_eq!(out.into_inner(), b"port 8080");
+    }
+
+    #[test]
+    fn shared_reference() {
+        let path =
+            std::env::temp_dir().join(format!("implementation-forward-{}", std::process::id()));
+        let file = Impl::new(
+            std::fs::File::options()
+                .create(true)
+                .truncate(true)
+                .read(true)
+                .write(true)
+                .open(&path)
+                .unwrap(),
+        );
+
+        (&file).write_all(b"0123456789").unwrap();
+        (&file).seek(SeekFrom::Start(6)).unwrap();
+        let mut rest = String::new();
+        (&file).read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "6789");
+
+        drop(file);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,137 @@
+use implementation::Impl;
+
+#[derive(PartialEq, Debug)]
+struct Config {
+    url: String,
+}
+
+#[test]
+fn option_methods_through_deref() {
+    let config = Config {
+        url: "postgres://".to_string(),
+    };
+    let found = Impl::new(Some(&config));
+
+    assert_eq!(found.map(|config| config.url.len()), Some(11));
+    assert_eq!(found.and_then(|config| config.url.find(':')), Some(8));
+    assert_eq!(found.zip(Some(5432)), Some((&config, 5432)));
+
+    let mut seen = None;
+    assert_eq!(
+        found.inspect(|config| seen = Some(config.url.clone())),
+        Some(&config)
+    );
+    assert_eq!(seen.as_deref(), Some("postgres://"));
+
+    let url = Impl::new(Some(String::from("postgres://")));
+    assert_eq!(url.as_deref(), Some("postgres://"));
+}
+
+#[test]
+fn combinators() {
+    let config = Impl::new("postgres://")
+        .map_impl(|url| Config {
+            url: url.to_string(),
+        })
+        .and_then_impl(|config| Impl::new(config.url))
+        .zip_impl(Impl::new(5432));
+    assert_eq!(config, Impl::new(("postgres://".to_string(), 5432)));
+
+    let mut seen = None;
+    let config = config.inspect_impl(|(_, port)| seen = Some(*port));
+    assert_eq!(seen, Some(5432));
+    assert_eq!(
+        config.map_impl(|(url, _)| url).as_deref_impl(),
+        Impl::new("postgres://")
+    );
+}
+
+#[test]
+fn from_mut() {
+    let mut url = String::from("postgres://");
+    let url_impl: &mut Impl<String> = Impl::from_mut(&mut url);
+    url_impl.push_str("localhost");
+    assert_eq!(url_impl.len(), 20);
+    assert_eq!(url, "postgres://localhost");
+}
+
+#[test]
+fn from_slice() {
+    let ports = [5432, 8080];
+    let impls: &[Impl<u16>] = Impl::from_slice(&ports);
+    assert_eq!(impls, [Impl::new(5432), Impl::new(8080)]);
+    assert_eq!(impls.iter().map(|port| **port).sum::<u16>(), 13512);
+}
+
+#[test]
+fn from_mut_slice() {
+    let mut ports = [5432, 8080];
+    let impls: &mut [Impl<u16>] = Impl::from_mut_slice(&mut ports);
+    *impls[0] += 1;
+    impls[1] = Impl::new(8443);
+    impls.swap(0, 1);
+    assert_eq!(ports, [8443, 5433]);
+}
+
+#[test]
+fn as_mut_impl() {
+    let mut url = Impl::new(String::from("postgres://"));
+    let mut borrowed: Impl<&mut String> = url.as_mut_impl();
+    borrowed.push_str("localhost");
+    assert_eq!(url, Impl::new(String::from("postgres://localhost")));
+}
+
+#[test]
+fn cloned_and_copied() {
+    let url = String::from("postgres://");
+    assert_eq!(Impl::new(&url).cloned_impl(), Impl::new(url.clone()));
+    assert_eq!(Impl::new(&5432).copied_impl(), Impl::new(5432));
+
+    let mut url = String::from("postgres://");
+    let mut port = 5432;
+    assert_eq!(
+        Impl::new(&mut url).cloned_impl(),
+        Impl::new(String::from("postgres://"))
+    );
+    assert_eq!(Impl::new(&mut port).copied_impl(), Impl::new(5432));
+
+    let mut port = Impl::new(5432);
+    let copied = port.as_mut_impl().copied_impl();
+    *port += 1;
+    assert_eq!((copied, port), (Impl::new(5432), Impl::new(5433)));
+}
+
+#[test]
+fn transpose() {
+    assert_eq!(
+        Impl::new(Some(5432)).transpose_impl(),
+        Some(Impl::new(5432))
+    );
+    assert_eq!(Impl::new(None::<u16>).transpose_impl(), None);
+
+    assert_eq!(
+        Impl::new(Ok::<_, String>(5432)).transpose_impl(),
+        Ok(Impl::new(5432))
+    );
+    assert_eq!(
+        Impl::new(Err::<u16, _>("no port")).transpose_impl(),
+        Err("no port")
+    );
+}
+
+#[test]
+fn select_is_not_shadowed() {
+    struct Html(&'static str);
+
+    impl Html {
+        fn select(&self, selector: &str) -> usize {
+            self.0.matches(selector).count()
+        }
+    }
+
+    let html = Impl::new(Html("<a class=\"link\"></a><a class=\"link\"></a>"));
+    assert_eq!(html.select("link"), 2);
+
+    let context = Impl::new((Html("<a class=\"link\"></a>"), 8080));
+    assert_eq!(context.select_impl::<Html, _>().select("link"), 1);
+}
@@ -0,0 +1,278 @@
+#![cfg(feature = "macros")]
+
+use std::cell::{Cell, RefCell};
+use std::fmt::Debug;
+use std::future::Future;
+
+use implementation::{Impl, Layer, Layered};
+
+mod common;
+
+use common::{block_on, yield_now};
+
+thread_local! {
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+}
+
+fn log(entry: String) {
+    LOG.with(|log| log.borrow_mut().push(entry));
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|log| log.take())
+}
+
+#[implementation::layered]
+trait Service {
+    fn attempt(&self, key: &'static str) -> Result<String, String>;
+    fn bump(&mut self) -> u32;
+    fn finish(self) -> u32;
+    async fn fetch(&self, key: &'static str) -> Result<String, String>;
+    async fn fetch_mut(&mut self) -> u32;
+}
+
+/// Fails the first `failures` calls.
+struct Flaky {
+    failures: u32,
+    calls: Cell<u32>,
+}
+
+impl Flaky {
+    fn new(failures: u32) -> Self {
+        Self {
+            failures,
+            calls: Cell::new(0),
+        }
+    }
+
+    fn call(&self, key: &str) -> Result<String, String> {
+        self.calls.set(self.calls.get() + 1);
+        log(format!("call {key}"));
+        if self.calls.get() > self.failures {
+            Ok(key.to_uppercase())
+        } else {
+            Err(format!("failure {}", self.calls.get()))
+        }
+    }
+}
+
+impl Service for Impl<Flaky> {
+    fn attempt(&self, key: &'static str) -> Result<String, String> {
+        self.call(key)
+    }
+
+    fn bump(&mut self) -> u32 {
+        self.calls.set(self.calls.get() + 1);
+        self.calls.get()
+    }
+
+    fn finish(self) -> u32 {
+        self.calls.get()
+    }
+
+    async fn fetch(&self, key: &'static str) -> Result<String, String> {
+        yield_now().await;
+        self.call(key)
+    }
+
+    async fn fetch_mut(&mut self) -> u32 {
+        yield_now().await;
+        self.bump()
+    }
+}
+
+/// Logs entering and exiting each call.
+struct Trace(&'static str);
+
+impl Layer for Trace {
+    fn around<A: Debug + Clone, R>(
+        &self,
+        method: &'static str,
+        args: A,
+        mut call: impl FnMut(A) -> R,
+    ) -> R {
+        log(format!("{} enter {method}{args:?}", self.0));
+        let output = call(args);
+        log(format!("{} exit {method}", self.0));
+        output
+    }
+
+    async fn around_async<A: Debug, F: Future>(
+        &self,
+        method: &'static str,
+        args: &A,
+        future: F,
+    ) -> F::Output {
+        log(format!("{} enter {method}{args:?}", self.0));
+        let output = future.await;
+        log(format!("{} exit {method}", self.0));
+        output
+    }
+}
+
+/// Makes every call `n` times, returning the output of the last call.
+struct Repeat(u32);
+
+impl Layer for Repeat {
+    fn around<A: Debug + Clone, R>(
+        &self,
+        _: &'static str,
+        args: A,
+        mut call: impl FnMut(A) -> R,
+    ) -> R {
+        for _ in 1..self.0 {
+            call(args.clone());
+        }
+        call(args)
+    }
+
+    fn around_async<A: Debug, F: Future>(
+        &self,
+        _: &'static str,
+        _: &A,
+        future: F,
+    ) -> impl Future<Output = F::Output> {
+        future
+    }
+}
+
+#[test]
+fn first_layer_is_outermost() {
+    let service = Layered::new((Trace("outer"), Trace("inner")), Flaky::new(0));
+    assert_eq!(service.attempt("a"), Ok("A".to_string()));
+    assert_eq!(
+        take_log(),
+        [
+            "outer enter attempt(\"a\",)",
+            "inner enter attempt(\"a\",)",
+            "call a",
+            "inner exit attempt",
+            "outer exit attempt",
+        ]
+    );
+}
+
+#[test]
+fn three_layers_compose_in_order() {
+    let service = Layered::new((Trace("1"), Trace("2"), Trace("3")), Flaky::new(0));
+    service.attempt("a").unwrap();
+    assert_eq!(
+        take_log(),
+        [
+            "1 enter attempt(\"a\",)",
+            "2 enter attempt(\"a\",)",
+            "3 enter attempt(\"a\",)",
+            "call a",
+            "3 exit attempt",
+            "2 exit attempt",
+            "1 exit attempt",
+        ]
+    );
+}
+
+#[test]
+fn async_layer_wraps_completion() {
+    let service = Layered::new((Trace("outer"), Trace("inner")), Flaky::new(0));
+    assert_eq!(block_on(service.fetch("a")), Ok("A".to_string()));
+    assert_eq!(
+        take_log(),
+        [
+            "outer enter fetch(\"a\",)",
+            "inner enter fetch(\"a\",)",
+            "call a",
+            "inner exit fetch",
+            "outer exit fetch",
+        ]
+    );
+}
+
+#[test]
+fn repeat() {
+    let service = Layered::new(Repeat(3), Flaky::new(2));
+    assert_eq!(service.attempt("a"), Ok("A".to_string()));
+    assert_eq!(take_log(), ["call a", "call a", "call a"]);
+
+    let service = Layered::new(Repeat(2), Flaky::new(2));
+    assert_eq!(service.attempt("a"), Err("failure 2".to_string()));
+}
+
+#[test]
+fn trace_around_repeat() {
+    let service = Layered::new((Trace("trace"), Repeat(2)), Flaky::new(1));
+    assert_eq!(service.attempt("a"), Ok("A".to_string()));
+    assert_eq!(
+        take_log(),
+        [
+            "trace enter attempt(\"a\",)",
+            "call a",
+            "call a",
+            "trace exit attempt",
+        ]
+    );
+}
+
+#[test]
+fn mutable_receiver_can_be_called_again() {
+    let mut service = Layered::new(Repeat(2), Flaky::new(0));
+    assert_eq!(service.bump(), 2);
+}
+
+#[test]
+#[should_panic(expected = "more than once")]
+fn owned_receiver_can_only_be_called_once() {
+    Layered::new(Repeat(2), Flaky::new(0)).finish();
+}
+
+#[test]
+fn async_method_is_called_once() {
+    let mut service = Layered::new(Repeat(2), Flaky::new(0));
+    assert_eq!(block_on(service.fetch_mut()), 1);
+}
+
+#[test]
+fn receivers_called_once() {
+    let mut service = Layered::new(Trace("trace"), Flaky::new(0));
+    assert_eq!(block_on(service.fetch_mut()), 1);
+    assert_eq!(service.finish(), 1);
+    assert_eq!(
+        take_log(),
+        [
+            "trace enter fetch_mut()",
+            "trace exit fetch_mut",
+            "trace enter finish()",
+            "trace exit finish",
+        ]
+    );
+}
+
+#[implementation::layered]
+trait Counter {
+    async fn get(&self) -> u32;
+    async fn increment(&mut self, by: u32) -> u32;
+    async fn finish(self) -> u32;
+}
+
+impl Counter for Impl<u32> {
+    async fn get(&self) -> u32 {
+        **self
+    }
+
+    async fn increment(&mut self, by: u32) -> u32 {
+        **self += by;
+        **self
+    }
+
+    async fn finish(self) -> u32 {
+        self.into_inner()
+    }
+}
+
+#[test]
+fn async_futures_are_send() {
+    fn assert_send<T: Send>(_: T) {}
+
+    let mut counter = Layered::new((Trace("outer"), (Repeat(2), ()), Trace("inner")), 0);
+    assert_send(counter.get());
+    assert_send(counter.increment(1));
+    assert_send(counter.finish());
+}
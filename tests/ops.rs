@@ -0,0 +1,60 @@
+use implementation::Impl;
+
+#[test]
+fn binary_operators() {
+    let (a, b) = (Impl::new(12), Impl::new(5));
+
+    assert_eq!(a + b, Impl::new(17));
+    assert_eq!(a - b, Impl::new(7));
+    assert_eq!(a * b, Impl::new(60));
+    assert_eq!(a / b, Impl::new(2));
+    assert_eq!(a % b, Impl::new(2));
+    assert_eq!(a & b, Impl::new(4));
+    assert_eq!(a | b, Impl::new(13));
+    assert_eq!(a ^ b, Impl::new(9));
+    assert_eq!(a << Impl::new(2), Impl::new(48));
+    assert_eq!(a >> Impl::new(2), Impl::new(3));
+}
+
+#[test]
+fn unary_operators() {
+    let a = Impl::new(12);
+
+    assert_eq!(-a, Impl::new(-12));
+    assert_eq!(!a, Impl::new(!12));
+}
+
+#[test]
+fn assign_operators() {
+    let (a, b) = (Impl::new(12), Impl::new(5));
+
+    let mut c = a;
+    c += b;
+    assert_eq!(c, Impl::new(17));
+    c -= b;
+    assert_eq!(c, Impl::new(12));
+    c *= b;
+    assert_eq!(c, Impl::new(60));
+    c /= b;
+    assert_eq!(c, Impl::new(12));
+    c %= b;
+    assert_eq!(c, Impl::new(2));
+    c |= b;
+    assert_eq!(c, Impl::new(7));
+    c &= b;
+    assert_eq!(c, Impl::new(5));
+    c ^= a;
+    assert_eq!(c, Impl::new(9));
+    c <<= Impl::new(1);
+    assert_eq!(c, Impl::new(18));
+    c >>= Impl::new(2);
+    assert_eq!(c, Impl::new(4));
+}
+
+#[test]
+fn index() {
+    let mut items = Impl::new(vec![1, 2, 3]);
+    items[0] = 10;
+    assert_eq!(items[0], 10);
+    assert_eq!(items[1..], [2, 3]);
+}
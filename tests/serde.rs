@@ -0,0 +1,70 @@
+#![cfg(feature = "serde")]
+
+use implementation::Impl;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Config {
+    url: String,
+    db: Impl<DbConfig>,
+    http: Option<Impl<HttpConfig>>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct DbConfig {
+    pool_size: u32,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct HttpConfig {
+    port: u16,
+}
+
+#[test]
+fn nested_impl_fields_round_trip() {
+    let json = r#"{"url":"postgres://","db":{"pool_size":4},"http":{"port":8080}}"#;
+    let config: Impl<Config> = serde_json::from_str(json).unwrap();
+    assert_eq!(config.db.pool_size, 4);
+    assert_eq!(config.http.as_ref().unwrap().port, 8080);
+    assert_eq!(serde_json::to_string(&config).unwrap(), json);
+}
+
+#[test]
+fn option_of_impl() {
+    let json = r#"{"url":"postgres://","db":{"pool_size":4},"http":null}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(config.http, None);
+    assert_eq!(serde_json::to_string(&config).unwrap(), json);
+
+    let port: Option<Impl<u16>> = serde_json::from_str("8080").unwrap();
+    assert_eq!(port, Some(Impl::new(8080)));
+}
+
+#[test]
+fn deserialization_error_is_unchanged() {
+    let json = r#"{"pool_size":"four"}"#;
+    let bare = serde_json::from_str::<DbConfig>(json).unwrap_err();
+    let wrapped = serde_json::from_str::<Impl<DbConfig>>(json).unwrap_err();
+    assert_eq!(wrapped.to_string(), bare.to_string());
+    assert_eq!(
+        (wrapped.line(), wrapped.column(), wrapped.classify()),
+        (bare.line(), bare.column(), bare.classify())
+    );
+}
+
+#[test]
+fn output_is_identical_to_bare_value() {
+    let db = DbConfig { pool_size: 4 };
+    assert_eq!(
+        serde_json::to_string(&Impl::new(&db)).unwrap(),
+        serde_json::to_string(&db).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(Impl::new(HttpConfig { port: 8080 })).unwrap(),
+        serde_json::to_value(HttpConfig { port: 8080 }).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_string(&Impl::new([1, 2, 3])).unwrap(),
+        "[1,2,3]"
+    );
+}
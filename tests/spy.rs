@@ -0,0 +1,105 @@
+#![cfg(all(feature = "macros", feature = "alloc"))]
+
+use implementation::{Call, Impl, Spy};
+
+mod common;
+
+use common::{block_on, yield_now};
+
+#[implementation::spy]
+trait Counter {
+    fn get(&self) -> u32;
+    fn increment(&mut self, by: u32) -> u32;
+    fn finish(self) -> u32;
+    fn zero() -> u32;
+    async fn fetch(&self, key: &str) -> String;
+    async fn fetch_mut(&mut self, by: u32) -> u32;
+}
+
+impl Counter for Impl<u32> {
+    fn get(&self) -> u32 {
+        **self
+    }
+
+    fn increment(&mut self, by: u32) -> u32 {
+        **self += by;
+        **self
+    }
+
+    fn finish(self) -> u32 {
+        self.into_inner()
+    }
+
+    fn zero() -> u32 {
+        0
+    }
+
+    async fn fetch(&self, key: &str) -> String {
+        yield_now().await;
+        format!("{key}={}", **self)
+    }
+
+    async fn fetch_mut(&mut self, by: u32) -> u32 {
+        yield_now().await;
+        self.increment(by)
+    }
+}
+
+fn call(
+    method: &'static str,
+    args: &[&dyn core::fmt::Debug],
+    output: &dyn core::fmt::Debug,
+) -> Call {
+    Call::new(method, args).returning(output)
+}
+
+#[test]
+fn shared_receiver_is_recorded() {
+    let spy = Spy::new(5);
+    assert_eq!(spy.get(), 5);
+    assert_eq!(spy.calls(), [call("get", &[], &5)]);
+}
+
+#[test]
+fn mutable_receiver_is_recorded() {
+    let mut spy = Spy::new(5);
+    assert_eq!(spy.increment(2), 7);
+    assert_eq!(spy.increment(3), 10);
+    assert_eq!(
+        spy.take_calls(),
+        [call("increment", &[&2], &7), call("increment", &[&3], &10)]
+    );
+    assert_eq!(spy.calls(), []);
+}
+
+#[test]
+fn owned_receiver_is_forwarded() {
+    // The call log is consumed along with the spy, so there's nowhere to record the call.
+    let mut spy = Spy::new(5);
+    spy.increment(1);
+    assert_eq!(spy.calls(), [call("increment", &[&1], &6)]);
+    assert_eq!(spy.finish(), 6);
+}
+
+#[test]
+fn no_receiver_is_not_recorded() {
+    let spy = Spy::new(5);
+    assert_eq!(<Spy<u32> as Counter>::zero(), 0);
+    assert_eq!(spy.calls(), []);
+}
+
+#[test]
+fn async_methods_are_recorded_on_completion() {
+    let mut spy = Spy::new(5);
+    let future = spy.fetch("count");
+    assert_eq!(spy.calls(), []);
+    assert_eq!(block_on(future), "count=5");
+    assert_eq!(block_on(spy.fetch_mut(2)), 7);
+    assert_eq!(
+        spy.calls(),
+        [
+            call("fetch", &[&"count"], &"count=5"),
+            call("fetch_mut", &[&2], &7),
+        ]
+    );
+}
@@ -0,0 +1,259 @@
+#![cfg(feature = "tracing")]
+
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use implementation::Impl;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+mod common;
+
+use common::block_on;
+
+/// A span or event, with its `Debug`-formatted fields.
+#[derive(Clone, PartialEq, Debug)]
+enum Captured {
+    Span(&'static str, Vec<(&'static str, String)>),
+    Event(Option<&'static str>, Vec<(&'static str, String)>),
+}
+
+/// Subscriber capturing spans, and events along with the span they happened in.
+#[derive(Clone, Default)]
+struct Capture {
+    captured: Arc<Mutex<Vec<Captured>>>,
+    spans: Arc<Mutex<Vec<&'static str>>>,
+    stack: Arc<Mutex<Vec<u64>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Capture {
+    fn run<R>(f: impl FnOnce() -> R) -> (R, Vec<Captured>) {
+        let capture = Capture::default();
+        let output = tracing::subscriber::with_default(capture.clone(), f);
+        let captured = capture.captured.lock().unwrap().clone();
+        (output, captured)
+    }
+}
+
+struct Fields(Vec<(&'static str, String)>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push((field.name(), format!("{value:?}")));
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(vec![]);
+        span.record(&mut fields);
+        let name = span.metadata().name();
+        self.captured
+            .lock()
+            .unwrap()
+            .push(Captured::Span(name, fields.0));
+        self.spans.lock().unwrap().push(name);
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(vec![]);
+        event.record(&mut fields);
+        let span = self
+            .stack
+            .lock()
+            .unwrap()
+            .last()
+            .map(|id| self.spans.lock().unwrap()[*id as usize - 1]);
+        self.captured
+            .lock()
+            .unwrap()
+            .push(Captured::Event(span, fields.0));
+    }
+
+    fn enter(&self, span: &Id) {
+        self.stack.lock().unwrap().push(span.into_u64());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.stack.lock().unwrap().pop();
+    }
+}
+
+trait GetUsername {
+    fn get_username(&self, id: u32) -> String;
+}
+
+trait Greet {
+    fn greet(&self, id: u32, greeting: &str) -> String;
+    fn greet_checked(&self, id: u32) -> Result<String, String>;
+}
+
+trait Entries {
+    fn get_mut(&mut self, n: usize) -> Result<&mut String, String>;
+}
+
+trait Fetch {
+    async fn fetch(&self, key: &str) -> Result<String, String>;
+}
+
+struct Config;
+
+#[implementation::instrument]
+impl GetUsername for Impl<Config> {
+    fn get_username(&self, id: u32) -> String {
+        tracing::info!("looking up");
+        format!("user{id}")
+    }
+}
+
+#[implementation::instrument]
+impl<T> Greet for Impl<T>
+where
+    Impl<T>: GetUsername,
+{
+    #[implementation(record(id, greeting))]
+    fn greet(&self, id: u32, greeting: &str) -> String {
+        format!("{greeting}, {}!", self.get_username(id))
+    }
+
+    #[implementation(record(id))]
+    fn greet_checked(&self, id: u32) -> Result<String, String> {
+        if id == 0 {
+            return Err("no such user".to_string());
+        }
+        Ok(self.greet(id, "Hi"))
+    }
+}
+
+#[implementation::instrument]
+impl Entries for Impl<Vec<String>> {
+    fn get_mut(&mut self, n: usize) -> Result<&mut String, String> {
+        self.as_mut_slice()
+            .get_mut(n)
+            .ok_or(format!("no entry {n}"))
+    }
+}
+
+#[implementation::instrument]
+impl<T> Fetch for Impl<T> {
+    async fn fetch(&self, key: &str) -> Result<String, String> {
+        tracing::info!("fetching");
+        let value = key.strip_prefix("ok:").ok_or("bad key")?;
+        Ok(value.to_string())
+    }
+}
+
+#[test]
+fn span_named_after_trait_and_method() {
+    let (output, captured) = Capture::run(|| Impl::new(Config).get_username(1));
+    assert_eq!(output, "user1");
+    assert_eq!(
+        captured,
+        [
+            Captured::Span("GetUsername::get_username", vec![]),
+            Captured::Event(
+                Some("GetUsername::get_username"),
+                vec![("message", "looking up".to_string())]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn records_selected_arguments() {
+    let (_, captured) = Capture::run(|| Impl::new(Config).greet(7, "Hello"));
+    assert_eq!(
+        captured[0],
+        Captured::Span(
+            "Greet::greet",
+            vec![
+                ("id", "7".to_string()),
+                ("greeting", "\"Hello\"".to_string())
+            ]
+        )
+    );
+    assert_eq!(
+        captured[1],
+        Captured::Span("GetUsername::get_username", vec![])
+    );
+}
+
+#[test]
+fn records_errors() {
+    let (output, captured) = Capture::run(|| Impl::new(Config).greet_checked(0));
+    assert!(output.is_err());
+    assert_eq!(
+        captured,
+        [
+            Captured::Span("Greet::greet_checked", vec![("id", "0".to_string())]),
+            Captured::Event(
+                Some("Greet::greet_checked"),
+                vec![("error", "\"no such user\"".to_string())]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn does_not_record_ok() {
+    let (output, captured) = Capture::run(|| Impl::new(Config).greet_checked(1));
+    assert_eq!(output.unwrap(), "Hi, user1!");
+    assert!(!captured.iter().any(|captured| matches!(
+        captured,
+        Captured::Event(_, fields) if fields.iter().any(|(name, _)| *name == "error")
+    )));
+}
+
+#[test]
+fn returns_borrow_of_mutable_receiver() {
+    let mut entries = Impl::new(vec!["a".to_string()]);
+    let (output, captured) = Capture::run(|| {
+        entries.get_mut(0).unwrap().push('b');
+        entries.get_mut(1).unwrap_err()
+    });
+    assert_eq!(output, "no entry 1");
+    assert_eq!(*entries, ["ab"]);
+    assert_eq!(
+        captured,
+        [
+            Captured::Span("Entries::get_mut", vec![]),
+            Captured::Span("Entries::get_mut", vec![]),
+            Captured::Event(
+                Some("Entries::get_mut"),
+                vec![("error", "\"no entry 1\"".to_string())]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn async_method_is_instrumented_across_await() {
+    let (output, captured) = Capture::run(|| block_on(Impl::new(()).fetch("bad")));
+    assert!(output.is_err());
+    assert_eq!(
+        captured,
+        [
+            Captured::Span("Fetch::fetch", vec![]),
+            Captured::Event(
+                Some("Fetch::fetch"),
+                vec![("message", "fetching".to_string())]
+            ),
+            Captured::Event(
+                Some("Fetch::fetch"),
+                vec![("error", "\"bad key\"".to_string())]
+            ),
+        ]
+    );
+}
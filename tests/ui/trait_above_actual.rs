@@ -0,0 +1,14 @@
+#[implementation::trait_]
+trait GetUrl {
+    fn get_url(&self) -> String;
+}
+
+#[implementation::trait_]
+#[implementation::actual]
+impl GetUrl for implementation::Impl {
+    fn get_url(&self) -> String {
+        String::new()
+    }
+}
+
+fn main() {}
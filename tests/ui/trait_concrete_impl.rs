@@ -0,0 +1,15 @@
+#[implementation::trait_]
+trait GetUrl {
+    fn get_url(&self) -> String;
+}
+
+struct Config;
+
+#[implementation::trait_]
+impl GetUrl for implementation::Impl<Config> {
+    fn get_url(&self) -> String {
+        String::new()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[implementation::trait_]
+struct Config;
+
+#[implementation::trait_(GetUrl)]
+trait GetPort {
+    fn get_port(&self) -> u16;
+}
+
+fn main() {}
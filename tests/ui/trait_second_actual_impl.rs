@@ -0,0 +1,28 @@
+use implementation::Impl;
+
+#[implementation::trait_]
+trait GetUrl {
+    fn get_url(&self) -> String;
+}
+
+trait Online {}
+
+#[implementation::trait_]
+impl<T> GetUrl for Impl<T>
+where
+    Impl<T>: Online,
+{
+    fn get_url(&self) -> String {
+        String::from("online")
+    }
+}
+
+#[implementation::actual]
+#[implementation::trait_]
+impl GetUrl for Impl {
+    fn get_url(&self) -> String {
+        String::from("offline")
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use implementation::Impl;
+
+#[implementation::trait_]
+trait GetUrl {
+    fn get_url(&self) -> String;
+}
+
+impl<T> GetUrl for Impl<T> {
+    fn get_url(&self) -> String {
+        String::new()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use implementation::Impl;
+
+trait GetUrl {
+    fn get_url(&self) -> String;
+}
+
+#[implementation::trait_]
+impl<T> GetUrl for Impl<T> {
+    fn get_url(&self) -> String {
+        String::new()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,163 @@
+#![cfg(feature = "unimock")]
+
+use implementation::Impl;
+use unimock::{matching, unimock, MockFn, Unimock};
+
+mod common;
+
+use common::block_on;
+
+#[unimock(api = GetUsernameMock)]
+trait GetUsername {
+    fn get_username(&self, id: u32) -> String;
+}
+
+#[implementation::unmock]
+#[unimock(api = GreetMock)]
+trait Greet {
+    fn greet(&self, id: u32) -> String;
+    fn greet_all(&self, ids: &[u32]) -> Vec<String>;
+}
+
+impl<T: GetUsername + Greet> Greet for Impl<T> {
+    fn greet(&self, id: u32) -> String {
+        format!("Hello, {}!", self.get_username(id))
+    }
+
+    fn greet_all(&self, ids: &[u32]) -> Vec<String> {
+        ids.iter().map(|id| Greet::greet(&**self, *id)).collect()
+    }
+}
+
+#[implementation::unmock]
+#[unimock(api = CounterMock)]
+trait Counter {
+    fn increment(&mut self, by: u32) -> u32;
+    fn finish(self) -> &'static str;
+}
+
+impl<T> Counter for Impl<T> {
+    fn increment(&mut self, by: u32) -> u32 {
+        by + 1
+    }
+
+    fn finish(self) -> &'static str {
+        "finished"
+    }
+}
+
+#[implementation::unmock]
+#[unimock(api = FetchMock)]
+trait Fetch {
+    async fn fetch(&self, key: &str) -> String;
+}
+
+impl<T> Fetch for Impl<T> {
+    async fn fetch(&self, key: &str) -> String {
+        format!("fetched {key}")
+    }
+}
+
+#[implementation::trait_]
+#[implementation::unmock]
+#[unimock(api = LookupMock)]
+trait Lookup<K> {
+    fn lookup(&self, key: K) -> Option<K>;
+}
+
+#[implementation::trait_]
+impl<T, K> Lookup<K> for Impl<T> {
+    fn lookup(&self, key: K) -> Option<K> {
+        Some(key)
+    }
+}
+
+fn username_42() -> impl unimock::Clause {
+    GetUsernameMock::get_username
+        .next_call(matching!(42))
+        .returns("Alice".to_string())
+}
+
+#[test]
+fn partial_mock_calls_actual_implementation() {
+    let deps = Unimock::new_partial(username_42());
+    assert_eq!(deps.greet(42), "Hello, Alice!");
+}
+
+#[test]
+fn actual_implementation_calls_back_through_unimock() {
+    let deps = Unimock::new_partial((
+        GetUsernameMock::get_username
+            .each_call(matching!(1))
+            .returns("Alice".to_string()),
+        GreetMock::greet
+            .each_call(matching!(2))
+            .returns("Hi, Bob!".to_string()),
+    ));
+    assert_eq!(
+        deps.greet_all(&[1, 2, 1]),
+        ["Hello, Alice!", "Hi, Bob!", "Hello, Alice!"]
+    );
+}
+
+#[test]
+fn partial_mock_overrides_actual_implementation() {
+    let deps = Unimock::new_partial(
+        GreetMock::greet
+            .next_call(matching!(42))
+            .returns("Mocked!".to_string()),
+    );
+    assert_eq!(deps.greet(42), "Mocked!");
+}
+
+#[test]
+fn strict_mock_applies_unmocked() {
+    let deps = Unimock::new((
+        GreetMock::greet.next_call(matching!(42)).applies_unmocked(),
+        username_42(),
+    ));
+    assert_eq!(deps.greet(42), "Hello, Alice!");
+}
+
+#[test]
+#[should_panic(expected = "Greet::greet(42): No mock implementation found.")]
+fn strict_mock_does_not_call_actual_implementation() {
+    let deps = Unimock::new(());
+    deps.greet(42);
+}
+
+#[test]
+fn owned_receiver() {
+    let deps = Unimock::new_partial(());
+    assert_eq!(deps.finish(), "finished");
+}
+
+#[test]
+#[should_panic(expected = "Counter::increment cannot be unmocked")]
+fn mutable_receiver_is_not_unmocked() {
+    let mut deps = Unimock::new_partial(());
+    deps.increment(1);
+}
+
+#[test]
+fn mutable_receiver_is_mocked() {
+    let mut deps = Unimock::new_partial(
+        CounterMock::increment
+            .next_call(matching!(1))
+            .returns(3_u32),
+    );
+    assert_eq!(deps.increment(1), 3);
+}
+
+#[test]
+fn async_method() {
+    let deps = Unimock::new_partial(());
+    let output = block_on(deps.fetch("key"));
+    assert_eq!(output, "fetched key");
+}
+
+#[test]
+fn generic_trait() {
+    let deps = Unimock::new_partial(());
+    assert_eq!(deps.lookup(42_u8), Some(42));
+}